@@ -1,3 +1,4 @@
+use crate::event::Readiness;
 use crate::{sys, Token};
 
 use std::fmt;
@@ -183,6 +184,28 @@ impl Event {
         sys::event::is_lio(&self.inner)
     }
 
+    /// Returns the readiness of the event as a platform independent
+    /// [`Readiness`] set.
+    ///
+    /// This contains the readable, writable, error, read closed, write closed
+    /// and priority readiness, as returned by the other methods of `Event`.
+    ///
+    /// [`Readiness`]: struct.Readiness.html
+    pub fn readiness(&self) -> Readiness {
+        let flags = [
+            (self.is_readable(), Readiness::READABLE),
+            (self.is_writable(), Readiness::WRITABLE),
+            (self.is_error(), Readiness::ERROR),
+            (self.is_read_closed(), Readiness::READ_CLOSED),
+            (self.is_write_closed(), Readiness::WRITE_CLOSED),
+            (self.is_priority(), Readiness::PRIORITY),
+        ];
+        flags
+            .iter()
+            .filter(|(set, _)| *set)
+            .fold(Readiness::EMPTY, |readiness, (_, flag)| readiness | *flag)
+    }
+
     /// Create a reference to an `Event` from a platform specific event.
     pub(crate) fn from_sys_event_ref(sys_event: &sys::Event) -> &Event {
         unsafe {
@@ -1,4 +1,4 @@
-use crate::event::Event;
+use crate::event::{Event, OwnedEvent};
 use crate::sys;
 
 use std::fmt;
@@ -43,6 +43,20 @@ pub struct Events {
     inner: sys::Events,
 }
 
+/// Draining [`Events`] iterator.
+///
+/// This struct is created by the [`drain`] method on [`Events`]. Once dropped
+/// all events are removed from [`Events`], even if the iterator was not fully
+/// consumed.
+///
+/// [`Events`]: struct.Events.html
+/// [`drain`]: struct.Events.html#method.drain
+#[derive(Debug)]
+pub struct Drain<'a> {
+    inner: &'a mut Events,
+    pos: usize,
+}
+
 /// [`Events`] iterator.
 ///
 /// This struct is created by the [`iter`] method on [`Events`].
@@ -152,6 +166,46 @@ impl Events {
         }
     }
 
+    /// Returns an iterator that removes all events, returning them as
+    /// [`OwnedEvent`]s.
+    ///
+    /// Unlike [`Event`]s, which can only be accessed by reference, the
+    /// returned [`OwnedEvent`]s can be stored and send to other threads. Once
+    /// the iterator is dropped `Events` is empty.
+    ///
+    /// [`OwnedEvent`]: struct.OwnedEvent.html
+    /// [`Event`]: struct.Event.html
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use mio::event::OwnedEvent;
+    /// use std::time::Duration;
+    ///
+    /// let mut events = Events::with_capacity(1024);
+    /// let mut poll = Poll::new()?;
+    ///
+    /// // Register handles with `poll`.
+    ///
+    /// poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+    ///
+    /// let owned: Vec<OwnedEvent> = events.drain().collect();
+    /// assert!(events.is_empty());
+    /// # drop(owned);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_> {
+        Drain {
+            inner: self,
+            pos: 0,
+        }
+    }
+
     /// Clearing all `Event` values from container explicitly.
     ///
     /// # Notes
@@ -223,6 +277,31 @@ impl<'a> Iterator for Iter<'a> {
     }
 }
 
+impl<'a> Iterator for Drain<'a> {
+    type Item = OwnedEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ret = self
+            .inner
+            .inner
+            .get(self.pos)
+            .map(|event| OwnedEvent::from(Event::from_sys_event_ref(event)));
+        self.pos += 1;
+        ret
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.inner.inner.len().saturating_sub(self.pos);
+        (size, Some(size))
+    }
+}
+
+impl<'a> Drop for Drain<'a> {
+    fn drop(&mut self) {
+        self.inner.clear();
+    }
+}
+
 impl fmt::Debug for Events {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self).finish()
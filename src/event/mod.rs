@@ -3,8 +3,12 @@
 #[allow(clippy::module_inception)]
 mod event;
 mod events;
+mod owned;
+mod readiness;
 mod source;
 
 pub use self::event::Event;
-pub use self::events::{Events, Iter};
+pub use self::events::{Drain, Events, Iter};
+pub use self::owned::OwnedEvent;
+pub use self::readiness::Readiness;
 pub use self::source::Source;
@@ -0,0 +1,57 @@
+use crate::event::{Event, Readiness};
+use crate::Token;
+
+/// An owned readiness event.
+///
+/// `OwnedEvent` is a platform independent copy of an [`Event`], containing its
+/// token and [`Readiness`]. Unlike `Event`, which only lives inside
+/// [`Events`], an `OwnedEvent` can be stored, compared and sent to other
+/// threads.
+///
+/// `OwnedEvent`s can be created from `Event`s using the `From`
+/// implementation, or by using [`Events::drain`].
+///
+/// [`Event`]: struct.Event.html
+/// [`Readiness`]: struct.Readiness.html
+/// [`Events`]: struct.Events.html
+/// [`Events::drain`]: struct.Events.html#method.drain
+///
+/// # Examples
+///
+/// ```
+/// use mio::event::{OwnedEvent, Readiness};
+/// use mio::Token;
+///
+/// let event = OwnedEvent::new(Token(0), Readiness::READABLE);
+/// assert_eq!(event.token(), Token(0));
+/// assert!(event.readiness().is_readable());
+/// assert!(!event.readiness().is_writable());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OwnedEvent {
+    token: Token,
+    readiness: Readiness,
+}
+
+impl OwnedEvent {
+    /// Create a new `OwnedEvent`.
+    pub const fn new(token: Token, readiness: Readiness) -> OwnedEvent {
+        OwnedEvent { token, readiness }
+    }
+
+    /// Returns the event's token.
+    pub const fn token(&self) -> Token {
+        self.token
+    }
+
+    /// Returns the event's readiness.
+    pub const fn readiness(&self) -> Readiness {
+        self.readiness
+    }
+}
+
+impl From<&Event> for OwnedEvent {
+    fn from(event: &Event) -> OwnedEvent {
+        OwnedEvent::new(event.token(), event.readiness())
+    }
+}
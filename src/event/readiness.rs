@@ -0,0 +1,170 @@
+use std::{fmt, ops};
+
+/// A portable set of readiness.
+///
+/// `Readiness` is the readiness part of an [`Event`] in a platform independent
+/// form, making it possible to store, compare and send it to other threads.
+/// See [`OwnedEvent`].
+///
+/// [`Event`]: struct.Event.html
+/// [`OwnedEvent`]: struct.OwnedEvent.html
+#[derive(Copy, PartialEq, Eq, Clone, PartialOrd, Ord, Hash, Default)]
+pub struct Readiness(u8);
+
+// These must be unique.
+const READABLE: u8 = 0b00_0001;
+const WRITABLE: u8 = 0b00_0010;
+const ERROR: u8 = 0b00_0100;
+const READ_CLOSED: u8 = 0b00_1000;
+const WRITE_CLOSED: u8 = 0b01_0000;
+const PRIORITY: u8 = 0b10_0000;
+
+impl Readiness {
+    /// Returns an empty `Readiness` set.
+    pub const EMPTY: Readiness = Readiness(0);
+
+    /// Returns a `Readiness` set representing readable readiness.
+    pub const READABLE: Readiness = Readiness(READABLE);
+
+    /// Returns a `Readiness` set representing writable readiness.
+    pub const WRITABLE: Readiness = Readiness(WRITABLE);
+
+    /// Returns a `Readiness` set representing error readiness.
+    pub const ERROR: Readiness = Readiness(ERROR);
+
+    /// Returns a `Readiness` set representing read closed readiness.
+    pub const READ_CLOSED: Readiness = Readiness(READ_CLOSED);
+
+    /// Returns a `Readiness` set representing write closed readiness.
+    pub const WRITE_CLOSED: Readiness = Readiness(WRITE_CLOSED);
+
+    /// Returns a `Readiness` set representing priority readiness.
+    pub const PRIORITY: Readiness = Readiness(PRIORITY);
+
+    /// Add together two `Readiness`.
+    ///
+    /// This does the same thing as the `BitOr` implementation, but is a
+    /// constant function.
+    ///
+    /// ```
+    /// use mio::event::Readiness;
+    ///
+    /// const READINESS: Readiness = Readiness::READABLE.add(Readiness::WRITABLE);
+    /// assert!(READINESS.is_readable());
+    /// assert!(READINESS.is_writable());
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub const fn add(self, other: Readiness) -> Readiness {
+        Readiness(self.0 | other.0)
+    }
+
+    /// Removes `other` `Readiness` from `self`.
+    ///
+    /// ```
+    /// use mio::event::Readiness;
+    ///
+    /// let readiness = Readiness::READABLE | Readiness::READ_CLOSED;
+    /// assert_eq!(readiness.remove(Readiness::READ_CLOSED), Readiness::READABLE);
+    /// assert!(readiness.remove(readiness).is_empty());
+    /// ```
+    pub const fn remove(self, other: Readiness) -> Readiness {
+        Readiness(self.0 & !other.0)
+    }
+
+    /// Returns true if the set is empty.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns true if the value includes readable readiness.
+    pub const fn is_readable(self) -> bool {
+        (self.0 & READABLE) != 0
+    }
+
+    /// Returns true if the value includes writable readiness.
+    pub const fn is_writable(self) -> bool {
+        (self.0 & WRITABLE) != 0
+    }
+
+    /// Returns true if the value includes error readiness.
+    pub const fn is_error(self) -> bool {
+        (self.0 & ERROR) != 0
+    }
+
+    /// Returns true if the value includes read closed readiness.
+    pub const fn is_read_closed(self) -> bool {
+        (self.0 & READ_CLOSED) != 0
+    }
+
+    /// Returns true if the value includes write closed readiness.
+    pub const fn is_write_closed(self) -> bool {
+        (self.0 & WRITE_CLOSED) != 0
+    }
+
+    /// Returns true if the value includes priority readiness.
+    pub const fn is_priority(self) -> bool {
+        (self.0 & PRIORITY) != 0
+    }
+
+    /// Returns the raw bits of the `Readiness`, see [`Readiness::from_bits`].
+    // Used by kqueue to post events and by `record`.
+    #[cfg_attr(not(feature = "record"), allow(dead_code))]
+    pub(crate) const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Create `Readiness` from raw bits, returns `None` if `bits` contains
+    /// unknown readiness.
+    #[cfg_attr(not(feature = "record"), allow(dead_code))]
+    pub(crate) fn from_bits(bits: u8) -> Option<Readiness> {
+        let all = READABLE | WRITABLE | ERROR | READ_CLOSED | WRITE_CLOSED | PRIORITY;
+        if bits & !all == 0 {
+            Some(Readiness(bits))
+        } else {
+            None
+        }
+    }
+}
+
+impl ops::BitOr for Readiness {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        self.add(other)
+    }
+}
+
+impl ops::BitOrAssign for Readiness {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 = (*self | other).0;
+    }
+}
+
+impl fmt::Debug for Readiness {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags = [
+            (READABLE, "READABLE"),
+            (WRITABLE, "WRITABLE"),
+            (ERROR, "ERROR"),
+            (READ_CLOSED, "READ_CLOSED"),
+            (WRITE_CLOSED, "WRITE_CLOSED"),
+            (PRIORITY, "PRIORITY"),
+        ];
+        let mut one = false;
+        for (flag, name) in flags.iter() {
+            if self.0 & flag != 0 {
+                if one {
+                    write!(fmt, " | ")?
+                }
+                write!(fmt, "{}", name)?;
+                one = true
+            }
+        }
+        if !one {
+            write!(fmt, "(empty)")?;
+        }
+        Ok(())
+    }
+}
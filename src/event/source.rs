@@ -109,6 +109,44 @@ pub trait Source {
     ///
     /// [`Registry::deregister`]: ../struct.Registry.html#method.deregister
     fn deregister(&mut self, registry: &Registry) -> io::Result<()>;
+
+    /// Pause the registration of `self` with the given `Registry` instance.
+    ///
+    /// This function should not be called directly. Use [`Registry::pause`]
+    /// instead. Implementors should handle pausing by delegating the call to
+    /// another `Source` type.
+    ///
+    /// The default implementation returns an error, as not all event sources
+    /// support pausing.
+    ///
+    /// [`Registry::pause`]: ../struct.Registry.html#method.pause
+    fn pause(&mut self, registry: &Registry) -> io::Result<()> {
+        let _ = registry;
+        Err(pause_unsupported())
+    }
+
+    /// Resume the paused registration of `self` with the given `Registry`
+    /// instance.
+    ///
+    /// This function should not be called directly. Use [`Registry::resume`]
+    /// instead. Implementors should handle resuming by delegating the call to
+    /// another `Source` type.
+    ///
+    /// The default implementation returns an error, as not all event sources
+    /// support pausing.
+    ///
+    /// [`Registry::resume`]: ../struct.Registry.html#method.resume
+    fn resume(&mut self, registry: &Registry) -> io::Result<()> {
+        let _ = registry;
+        Err(pause_unsupported())
+    }
+}
+
+fn pause_unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "pausing registrations is not supported by this event source",
+    )
 }
 
 impl<T> Source for Box<T>
@@ -136,4 +174,12 @@ where
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         (**self).deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry) -> io::Result<()> {
+        (**self).pause(registry)
+    }
+
+    fn resume(&mut self, registry: &Registry) -> io::Result<()> {
+        (**self).resume(registry)
+    }
 }
@@ -113,6 +113,22 @@ impl Interest {
     pub const fn is_lio(self) -> bool {
         (self.0.get() & LIO) != 0
     }
+
+    /// Returns the raw bits of the `Interest`, see [`Interest::from_bits`].
+    #[cfg(feature = "record")]
+    pub(crate) const fn bits(self) -> u8 {
+        self.0.get()
+    }
+
+    /// Create `Interest` from raw bits, returns `None` if `bits` is zero or
+    /// contains unknown interests.
+    #[cfg(feature = "record")]
+    pub(crate) fn from_bits(bits: u8) -> Option<Interest> {
+        if bits & !(READABLE | WRITABLE | AIO | LIO) != 0 {
+            return None;
+        }
+        NonZeroU8::new(bits).map(Interest)
+    }
 }
 
 impl ops::BitOr for Interest {
@@ -62,6 +62,10 @@ use crate::{event, Interest, Registry, Token};
 pub struct IoSource<T> {
     state: IoSourceState,
     inner: T,
+    /// Token and interests the source is registered with, used to pause and
+    /// resume the registration.
+    #[cfg(unix)]
+    registration: Option<(Token, Interest)>,
     #[cfg(debug_assertions)]
     selector_id: SelectorId,
 }
@@ -72,6 +76,8 @@ impl<T> IoSource<T> {
         IoSource {
             state: IoSourceState::new(),
             inner: io,
+            #[cfg(unix)]
+            registration: None,
             #[cfg(debug_assertions)]
             selector_id: SelectorId::new(),
         }
@@ -142,9 +148,12 @@ where
     ) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.associate(registry)?;
-        registry
-            .selector()
-            .register(self.inner.as_raw_fd(), token, interests)
+        let fd = self.inner.as_raw_fd();
+        registry.selector().register(fd, token, interests)?;
+        #[cfg(feature = "record")]
+        registry.recorder().register(fd as u64, token, interests);
+        self.registration = Some((token, interests));
+        Ok(())
     }
 
     fn reregister(
@@ -155,15 +164,47 @@ where
     ) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.check_association(registry)?;
-        registry
-            .selector()
-            .reregister(self.inner.as_raw_fd(), token, interests)
+        let fd = self.inner.as_raw_fd();
+        registry.selector().reregister(fd, token, interests)?;
+        #[cfg(feature = "record")]
+        registry.recorder().reregister(fd as u64, token, interests);
+        self.registration = Some((token, interests));
+        Ok(())
     }
 
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.remove_association(registry)?;
-        registry.selector().deregister(self.inner.as_raw_fd())
+        let fd = self.inner.as_raw_fd();
+        registry.selector().deregister(fd)?;
+        #[cfg(feature = "record")]
+        registry
+            .recorder()
+            .deregister(fd as u64, self.registration.map(|(token, _)| token));
+        self.registration = None;
+        Ok(())
+    }
+
+    fn pause(&mut self, registry: &Registry) -> io::Result<()> {
+        #[cfg(debug_assertions)]
+        self.selector_id.check_association(registry)?;
+        let (token, interests) = self.registration.ok_or_else(not_registered)?;
+        let fd = self.inner.as_raw_fd();
+        registry.selector().pause(fd, token, interests)?;
+        #[cfg(feature = "record")]
+        registry.recorder().pause(fd as u64, token);
+        Ok(())
+    }
+
+    fn resume(&mut self, registry: &Registry) -> io::Result<()> {
+        #[cfg(debug_assertions)]
+        self.selector_id.check_association(registry)?;
+        let (token, interests) = self.registration.ok_or_else(not_registered)?;
+        let fd = self.inner.as_raw_fd();
+        registry.selector().resume(fd, token, interests)?;
+        #[cfg(feature = "record")]
+        registry.recorder().resume(fd as u64, token);
+        Ok(())
     }
 }
 
@@ -180,8 +221,11 @@ where
     ) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.associate(registry)?;
-        self.state
-            .register(registry, token, interests, self.inner.as_raw_socket())
+        let socket = self.inner.as_raw_socket();
+        self.state.register(registry, token, interests, socket)?;
+        #[cfg(feature = "record")]
+        registry.recorder().register(socket, token, interests);
+        Ok(())
     }
 
     fn reregister(
@@ -192,13 +236,25 @@ where
     ) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.check_association(registry)?;
-        self.state.reregister(registry, token, interests)
+        self.state.reregister(registry, token, interests)?;
+        #[cfg(feature = "record")]
+        registry
+            .recorder()
+            .reregister(self.inner.as_raw_socket(), token, interests);
+        Ok(())
     }
 
     fn deregister(&mut self, _registry: &Registry) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.remove_association(_registry)?;
-        self.state.deregister()
+        #[cfg(feature = "record")]
+        let token = self.state.token();
+        self.state.deregister()?;
+        #[cfg(feature = "record")]
+        _registry
+            .recorder()
+            .deregister(self.inner.as_raw_socket(), token);
+        Ok(())
     }
 }
 
@@ -215,9 +271,11 @@ where
     ) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.associate(registry)?;
-        registry
-            .selector()
-            .register(self.inner.as_raw_fd() as _, token, interests)
+        let fd = self.inner.as_raw_fd();
+        registry.selector().register(fd as _, token, interests)?;
+        #[cfg(feature = "record")]
+        registry.recorder().register(fd as u64, token, interests);
+        Ok(())
     }
 
     fn reregister(
@@ -228,18 +286,32 @@ where
     ) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.check_association(registry)?;
-        registry
-            .selector()
-            .reregister(self.inner.as_raw_fd() as _, token, interests)
+        let fd = self.inner.as_raw_fd();
+        registry.selector().reregister(fd as _, token, interests)?;
+        #[cfg(feature = "record")]
+        registry.recorder().reregister(fd as u64, token, interests);
+        Ok(())
     }
 
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.remove_association(registry)?;
-        registry.selector().deregister(self.inner.as_raw_fd() as _)
+        let fd = self.inner.as_raw_fd();
+        registry.selector().deregister(fd as _)?;
+        #[cfg(feature = "record")]
+        registry.recorder().deregister(fd as u64, None);
+        Ok(())
     }
 }
 
+#[cfg(unix)]
+fn not_registered() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        "I/O source not registered with `Registry`",
+    )
+}
+
 impl<T> fmt::Debug for IoSource<T>
 where
     T: fmt::Debug,
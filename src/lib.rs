@@ -53,6 +53,10 @@ mod waker;
 
 pub mod event;
 
+cfg_record! {
+    pub mod record;
+}
+
 cfg_io_source! {
     mod io_source;
 }
@@ -117,6 +121,12 @@ pub mod features {
     #![cfg_attr(not(feature = "net"), doc = "## Network types (disabled)")]
     //!
     //! The `net` feature enables networking primitives in the `net` module.
+    //!
+    #![cfg_attr(feature = "record", doc = "## `record` (enabled)")]
+    #![cfg_attr(not(feature = "record"), doc = "## `record` (disabled)")]
+    //!
+    //! `record` enables recording the events returned by `Poll` and replaying
+    //! them later, see the `record` module. It implies `os-poll`.
 }
 
 pub mod guide {
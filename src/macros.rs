@@ -35,6 +35,17 @@ macro_rules! cfg_os_ext {
     }
 }
 
+/// The `record` feature is enabled.
+macro_rules! cfg_record {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "record")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "record")))]
+            $item
+        )*
+    }
+}
+
 /// The `net` feature is enabled.
 macro_rules! cfg_net {
     ($($item:item)*) => {
@@ -68,3 +79,63 @@ macro_rules! cfg_any_os_ext {
         )*
     }
 }
+
+/// The target supports setting the TCP keepalive time, interval and retries,
+/// see `TcpKeepalive`.
+macro_rules! cfg_tcp_keepalive {
+    ($($item:item)*) => {
+        $(
+            #[cfg(any(
+                target_os = "android",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "fuchsia",
+                target_os = "ios",
+                target_os = "linux",
+                target_os = "macos",
+                target_os = "netbsd",
+            ))]
+            #[cfg_attr(
+                docsrs,
+                doc(cfg(any(
+                    target_os = "android",
+                    target_os = "dragonfly",
+                    target_os = "freebsd",
+                    target_os = "fuchsia",
+                    target_os = "ios",
+                    target_os = "linux",
+                    target_os = "macos",
+                    target_os = "netbsd",
+                )))
+            )]
+            $item
+        )*
+    }
+}
+
+/// The target supports sending a file to a socket without copying it through
+/// user space, see `TcpStream::send_file`.
+macro_rules! cfg_send_file {
+    ($($item:item)*) => {
+        $(
+            #[cfg(any(
+                target_os = "android",
+                target_os = "freebsd",
+                target_os = "ios",
+                target_os = "linux",
+                target_os = "macos",
+            ))]
+            #[cfg_attr(
+                docsrs,
+                doc(cfg(any(
+                    target_os = "android",
+                    target_os = "freebsd",
+                    target_os = "ios",
+                    target_os = "linux",
+                    target_os = "macos",
+                )))
+            )]
+            $item
+        )*
+    }
+}
@@ -0,0 +1,170 @@
+#[cfg(unix)]
+use std::fs::File;
+use std::io;
+
+/// Call `accept` until it returns `WouldBlock`, or `max` connections are
+/// accepted, adding the accepted connections to `accepted`.
+///
+/// Connections aborted before they were accepted are skipped. Returns the
+/// number of accepted connections, or an error if the first call to `accept`
+/// failed. Errors after the first connection are dropped, an error that
+/// persists (e.g. running out of file descriptors) is returned by the next
+/// call.
+pub(crate) fn accept_many<T, F>(mut accept: F, accepted: &mut Vec<T>, max: usize) -> io::Result<usize>
+where
+    F: FnMut() -> io::Result<T>,
+{
+    let mut n = 0;
+    while n < max {
+        match accept() {
+            Ok(connection) => {
+                accepted.push(connection);
+                n += 1;
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            // The peer aborted the connection before it was accepted, try the
+            // next one.
+            Err(ref err) if err.kind() == io::ErrorKind::ConnectionAborted => continue,
+            Err(err) if n == 0 => return Err(err),
+            Err(_) => break,
+        }
+    }
+    Ok(n)
+}
+
+/// A reserved file descriptor, used to keep accepting connections once the
+/// process ran out of file descriptors.
+///
+/// When the file descriptor limit is reached accepting a connection fails
+/// with `EMFILE` (or `ENFILE`), but the connection stays in the backlog of the
+/// listener. This means the listener stays readable and, when using
+/// edge-triggered events, no new event is returned for it. To make progress
+/// [`TcpListener::accept_or_shed`] and [`UnixListener::accept_or_shed`] close
+/// the reserved file descriptor, accept the pending connection, close it
+/// right away and then reserve a file descriptor again.
+///
+/// [`TcpListener::accept_or_shed`]: crate::net::TcpListener::accept_or_shed
+/// [`UnixListener::accept_or_shed`]: crate::net::UnixListener::accept_or_shed
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io;
+///
+/// use mio::net::{FdReserve, TcpListener};
+///
+/// let listener = TcpListener::bind("127.0.0.1:0".parse()?)?;
+/// // Reserve a file descriptor before running out of them.
+/// let mut reserve = FdReserve::new()?;
+/// // Reset the shed connections, rather than closing them gracefully.
+/// reserve.set_reset(true);
+///
+/// loop {
+///     match listener.accept_or_shed(&mut reserve) {
+///         Ok(Some((connection, address))) => {
+///             // Handle the connection.
+/// #           drop((connection, address));
+///         }
+///         // The connection was shed, we can continue accepting.
+///         Ok(None) => continue,
+///         Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+///         // Includes running out of file descriptors without being able to
+///         // shed the connection, see `FdReserve::is_exhausted`.
+///         Err(err) => return Err(err.into()),
+///     }
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct FdReserve {
+    /// `None` if the file descriptor couldn't be reserved again after shedding
+    /// a connection.
+    fd: Option<File>,
+    reset: bool,
+}
+
+#[cfg(unix)]
+impl FdReserve {
+    /// Reserve a file descriptor, by opening `/dev/null`.
+    pub fn new() -> io::Result<FdReserve> {
+        Ok(FdReserve {
+            fd: Some(reserve_fd()?),
+            reset: false,
+        })
+    }
+
+    /// Set whether or not shed TCP connections are reset (by sending a RST)
+    /// rather than closed gracefully. Defaults to `false`.
+    ///
+    /// This has no effect on Unix domain sockets.
+    pub fn set_reset(&mut self, reset: bool) {
+        self.reset = reset;
+    }
+
+    /// Returns true if `err` indicates the process or system ran out of file
+    /// descriptors, i.e. `EMFILE` or `ENFILE`.
+    pub fn is_exhausted(err: &io::Error) -> bool {
+        matches!(err.raw_os_error(), Some(libc::EMFILE) | Some(libc::ENFILE))
+    }
+
+    /// Close the reserved file descriptor, call `accept_and_close` and
+    /// reserve a file descriptor again.
+    ///
+    /// Returns the result of `accept_and_close`, or `None` if no file
+    /// descriptor is reserved.
+    pub(crate) fn shed<F>(&mut self, accept_and_close: F) -> Option<io::Result<()>>
+    where
+        F: FnOnce() -> io::Result<()>,
+    {
+        // Try to reserve a file descriptor again if that failed previously.
+        let fd = match self.fd.take() {
+            Some(fd) => fd,
+            None => reserve_fd().ok()?,
+        };
+        drop(fd);
+        let result = accept_and_close();
+        self.fd = reserve_fd().ok();
+        Some(result)
+    }
+}
+
+/// Call `accept`, using `reserve` to shed the pending connection if no more
+/// file descriptors are available. `close` is called with the shed connection
+/// and whether or not it should be reset.
+///
+/// Returns `Ok(None)` if a connection was shed.
+#[cfg(unix)]
+pub(crate) fn accept_or_shed<T, A, C>(
+    accept: A,
+    reserve: &mut FdReserve,
+    close: C,
+) -> io::Result<Option<T>>
+where
+    A: Fn() -> io::Result<T>,
+    C: FnOnce(T, bool) -> io::Result<()>,
+{
+    match accept() {
+        Ok(connection) => Ok(Some(connection)),
+        Err(err) if FdReserve::is_exhausted(&err) => {
+            let reset = reserve.reset;
+            match reserve.shed(|| accept().and_then(|connection| close(connection, reset))) {
+                Some(Ok(())) => Ok(None),
+                // Includes `WouldBlock` if no connection was pending.
+                Some(Err(shed_err)) => Err(shed_err),
+                // No file descriptor to shed the connection with.
+                None => Err(err),
+            }
+        }
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(unix)]
+fn reserve_fd() -> io::Result<File> {
+    File::open("/dev/null")
+}
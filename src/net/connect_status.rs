@@ -0,0 +1,35 @@
+use std::io;
+
+/// Status of a non-blocking connect, as returned by
+/// [`TcpStream::finish_connect`] and `UnixStream::finish_connect`.
+///
+/// [`TcpStream::finish_connect`]: crate::net::TcpStream::finish_connect
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ConnectStatus {
+    /// The connection is established, the stream can be used.
+    Connected,
+    /// The connection is still being established, wait for another
+    /// (writable) event.
+    InProgress,
+}
+
+/// Determine the status of a non-blocking connect, using `take_error` to get
+/// the connect error (`SO_ERROR`) and `peer_addr` to check if the stream is
+/// connected.
+pub(crate) fn finish_connect<T>(
+    take_error: io::Result<Option<io::Error>>,
+    peer_addr: impl FnOnce() -> io::Result<T>,
+) -> io::Result<ConnectStatus> {
+    if let Some(err) = take_error? {
+        return Err(err);
+    }
+    match peer_addr() {
+        Ok(_) => Ok(ConnectStatus::Connected),
+        Err(err) if err.kind() == io::ErrorKind::NotConnected => Ok(ConnectStatus::InProgress),
+        #[cfg(unix)]
+        Err(err) if err.raw_os_error() == Some(libc::EINPROGRESS) => {
+            Ok(ConnectStatus::InProgress)
+        }
+        Err(err) => Err(err),
+    }
+}
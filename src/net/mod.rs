@@ -25,14 +25,43 @@
 //! <https://github.com/rust-lang/rust/issues/55794>. The best advice we can
 //! give is to always call receive with a large enough buffer.
 
+mod accept;
+#[cfg(unix)]
+pub use self::accept::FdReserve;
+
+mod connect_status;
+pub use self::connect_status::ConnectStatus;
+
 mod tcp;
+cfg_tcp_keepalive! {
+    pub use self::tcp::TcpKeepalive;
+}
+#[cfg(target_os = "linux")]
+pub use self::tcp::TcpInfo;
+#[cfg(not(target_os = "wasi"))]
+pub use self::tcp::{TcpConnect, TcpSocket};
 pub use self::tcp::{TcpListener, TcpStream};
 
+#[cfg(unix)]
+mod msg_flags;
+#[cfg(unix)]
+pub use self::msg_flags::MsgFlags;
+
 #[cfg(not(target_os = "wasi"))]
 mod udp;
 #[cfg(not(target_os = "wasi"))]
 pub use self::udp::UdpSocket;
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod recv_meta;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use self::recv_meta::RecvMeta;
+
+#[cfg(target_os = "linux")]
+mod zerocopy;
+#[cfg(target_os = "linux")]
+pub use self::zerocopy::ZeroCopyCompletion;
+
 #[cfg(unix)]
 mod uds;
 #[cfg(unix)]
@@ -0,0 +1,176 @@
+use std::{fmt, ops};
+
+/// Flags for the `send` and `recv` family of system calls, used by methods
+/// such as [`TcpStream::send_with_flags`] and
+/// [`UdpSocket::recv_from_with_flags`].
+///
+/// Flags can be combined using `|`. See the `send(2)` and `recv(2)` manual
+/// pages for the meaning of the flags.
+///
+/// [`TcpStream::send_with_flags`]: crate::net::TcpStream::send_with_flags
+/// [`UdpSocket::recv_from_with_flags`]: crate::net::UdpSocket::recv_from_with_flags
+///
+/// # Examples
+///
+/// ```
+/// use mio::net::MsgFlags;
+///
+/// let flags = MsgFlags::PEEK | MsgFlags::WAITALL;
+/// assert!(flags.contains(MsgFlags::PEEK));
+/// assert!(!flags.contains(MsgFlags::OOB));
+/// assert_eq!(format!("{:?}", flags), "PEEK | WAITALL");
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Default)]
+pub struct MsgFlags(libc::c_int);
+
+impl MsgFlags {
+    /// No flags set.
+    pub const EMPTY: MsgFlags = MsgFlags(0);
+
+    /// `MSG_PEEK`: receive data without removing it from the receive queue.
+    pub const PEEK: MsgFlags = MsgFlags(libc::MSG_PEEK);
+
+    /// `MSG_WAITALL`: block until the full request is satisfied. Has no effect
+    /// on non-blocking sockets on most platforms.
+    pub const WAITALL: MsgFlags = MsgFlags(libc::MSG_WAITALL);
+
+    /// `MSG_TRUNC`: on Linux, return the real length of a datagram, even if it
+    /// was longer than the buffer.
+    ///
+    /// **Note**: with this flag the returned length can be larger than the
+    /// buffer, so it can't be used to slice the buffer directly.
+    pub const TRUNC: MsgFlags = MsgFlags(libc::MSG_TRUNC);
+
+    /// `MSG_OOB`: send or receive out-of-band data.
+    pub const OOB: MsgFlags = MsgFlags(libc::MSG_OOB);
+
+    /// `MSG_MORE`: more data will be sent, delay sending partial packets.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub const MORE: MsgFlags = MsgFlags(libc::MSG_MORE);
+
+    /// `MSG_NOSIGNAL`: don't raise `SIGPIPE` if the peer closed the
+    /// connection, only return a `BrokenPipe` error.
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(
+            target_os = "android",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "illumos",
+            target_os = "linux",
+            target_os = "netbsd",
+            target_os = "openbsd",
+        )))
+    )]
+    pub const NOSIGNAL: MsgFlags = MsgFlags(libc::MSG_NOSIGNAL);
+
+    /// Add together two `MsgFlags`.
+    ///
+    /// This does the same thing as the `BitOr` implementation, but is a
+    /// constant function.
+    #[allow(clippy::should_implement_trait)]
+    pub const fn add(self, other: MsgFlags) -> MsgFlags {
+        MsgFlags(self.0 | other.0)
+    }
+
+    /// Returns true if all flags in `other` are set in `self`.
+    pub const fn contains(self, other: MsgFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub(crate) const fn bits(self) -> libc::c_int {
+        self.0
+    }
+}
+
+impl ops::BitOr for MsgFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        self.add(other)
+    }
+}
+
+impl ops::BitOrAssign for MsgFlags {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 = (*self | other).0;
+    }
+}
+
+impl fmt::Debug for MsgFlags {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut one = false;
+        if self.contains(MsgFlags::PEEK) {
+            if one {
+                write!(fmt, " | ")?
+            }
+            write!(fmt, "PEEK")?;
+            one = true
+        }
+        if self.contains(MsgFlags::WAITALL) {
+            if one {
+                write!(fmt, " | ")?
+            }
+            write!(fmt, "WAITALL")?;
+            one = true
+        }
+        if self.contains(MsgFlags::TRUNC) {
+            if one {
+                write!(fmt, " | ")?
+            }
+            write!(fmt, "TRUNC")?;
+            one = true
+        }
+        if self.contains(MsgFlags::OOB) {
+            if one {
+                write!(fmt, " | ")?
+            }
+            write!(fmt, "OOB")?;
+            one = true
+        }
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        {
+            if self.contains(MsgFlags::MORE) {
+                if one {
+                    write!(fmt, " | ")?
+                }
+                write!(fmt, "MORE")?;
+                one = true
+            }
+        }
+        #[cfg(any(
+            target_os = "android",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "illumos",
+            target_os = "linux",
+            target_os = "netbsd",
+            target_os = "openbsd",
+        ))]
+        {
+            if self.contains(MsgFlags::NOSIGNAL) {
+                if one {
+                    write!(fmt, " | ")?
+                }
+                write!(fmt, "NOSIGNAL")?;
+                one = true
+            }
+        }
+        if !one {
+            write!(fmt, "(empty)")?
+        }
+        Ok(())
+    }
+}
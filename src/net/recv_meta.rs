@@ -0,0 +1,76 @@
+use std::net::{Ipv4Addr, SocketAddr};
+
+/// Metadata of a received datagram, as returned by [`UdpSocket::recv_many`]
+/// and [`UdpSocket::recv_with_meta`].
+///
+/// [`UdpSocket::recv_many`]: crate::net::UdpSocket::recv_many
+/// [`UdpSocket::recv_with_meta`]: crate::net::UdpSocket::recv_with_meta
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RecvMeta {
+    pub(crate) len: usize,
+    pub(crate) addr: SocketAddr,
+    pub(crate) segment_size: Option<usize>,
+    pub(crate) truncated: bool,
+    pub(crate) control_truncated: bool,
+}
+
+impl RecvMeta {
+    /// Returns the number of bytes received.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the datagram was empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the address of the sender.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the size of the datagrams if the kernel coalesced multiple
+    /// datagrams from the sender into the buffer, see
+    /// [`UdpSocket::set_gro`]. All datagrams have this size, except the last
+    /// one which may be smaller.
+    ///
+    /// [`UdpSocket::set_gro`]: crate::net::UdpSocket::set_gro
+    ///
+    /// If [`is_control_truncated`] returns true the segment size may be
+    /// missing, even if the datagrams were coalesced.
+    ///
+    /// [`is_control_truncated`]: RecvMeta::is_control_truncated
+    pub fn segment_size(&self) -> Option<usize> {
+        self.segment_size
+    }
+
+    /// Returns true if the datagram was larger than the buffer, i.e. the
+    /// `MSG_TRUNC` flag was set. The excess bytes are discarded, [`len`]
+    /// returns the number of bytes stored in the buffer.
+    ///
+    /// [`len`]: RecvMeta::len
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Returns true if some control data was discarded, i.e. the `MSG_CTRUNC`
+    /// flag was set.
+    pub fn is_control_truncated(&self) -> bool {
+        self.control_truncated
+    }
+}
+
+/// An empty datagram from `0.0.0.0:0`, allowing `RecvMeta` arrays to be
+/// created before calling `recv_many`.
+impl Default for RecvMeta {
+    fn default() -> RecvMeta {
+        RecvMeta {
+            len: 0,
+            addr: SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)),
+            segment_size: None,
+            truncated: false,
+            control_truncated: false,
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use std::{fmt, io};
+
+use crate::net::{ConnectStatus, TcpStream};
+use crate::{event, Interest, Registry, Token};
+
+/// Default delay between connection attempts, the "Connection Attempt Delay"
+/// recommended in RFC 8305.
+const DEFAULT_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// A non-blocking connect to one of multiple addresses, racing the connection
+/// attempts using the "Happy Eyeballs" algorithm of [RFC 8305].
+///
+/// The addresses are sorted to alternate between address families, starting
+/// with the family of the first address, and are attempted in turn. A new
+/// attempt is started when the previous attempt failed, or when the
+/// [attempt delay] expired without a connection being established, while the
+/// earlier attempts keep running.
+///
+/// `TcpConnect` is an [`event::Source`], all connection attempts are
+/// registered with the same token. Once an event for that token is received,
+/// or the [`timeout`] expired, call [`try_connect`] to check the connection
+/// attempts, passing the registry `TcpConnect` is registered with so that new
+/// attempts can be registered. It returns the first established connection,
+/// closing all other attempts.
+///
+/// [RFC 8305]: https://tools.ietf.org/html/rfc8305
+/// [attempt delay]: TcpConnect::set_attempt_delay
+/// [`timeout`]: TcpConnect::timeout
+/// [`try_connect`]: TcpConnect::try_connect
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::TcpConnect;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// # let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+/// # let addr = listener.local_addr()?;
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(128);
+///
+/// // Usually the result of a DNS lookup.
+/// let addrs = vec!["[::1]:9".parse()?, addr];
+/// let mut connect = TcpConnect::new(addrs)?;
+/// poll.registry()
+///     .register(&mut connect, Token(0), Interest::WRITABLE)?;
+///
+/// let stream = loop {
+///     poll.poll(&mut events, connect.timeout())?;
+///     if let Some(stream) = connect.try_connect(poll.registry())? {
+///         break stream;
+///     }
+/// };
+/// // The stream is still registered with `Token(0)`.
+/// # drop(stream);
+/// #     Ok(())
+/// # }
+/// ```
+pub struct TcpConnect {
+    /// Addresses not yet attempted.
+    addrs: VecDeque<SocketAddr>,
+    /// Connection attempts in progress.
+    attempts: Vec<TcpStream>,
+    /// Token and interests all connection attempts are registered with.
+    registration: Option<(Token, Interest)>,
+    attempt_delay: Duration,
+    /// Time at which the last connection attempt was started.
+    last_attempt: Instant,
+    /// Error of the last failed connection attempt.
+    last_error: Option<io::Error>,
+}
+
+impl TcpConnect {
+    /// Start connecting to `addrs`.
+    ///
+    /// This starts the first connection attempt. Returns an error if `addrs`
+    /// is empty.
+    pub fn new<I>(addrs: I) -> io::Result<TcpConnect>
+    where
+        I: IntoIterator<Item = SocketAddr>,
+    {
+        let addrs = interleave(addrs.into_iter().collect());
+        if addrs.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "no addresses to connect to",
+            ));
+        }
+
+        let mut connect = TcpConnect {
+            addrs,
+            attempts: Vec::new(),
+            registration: None,
+            attempt_delay: DEFAULT_ATTEMPT_DELAY,
+            last_attempt: Instant::now(),
+            last_error: None,
+        };
+        // Not registered yet, so there is no registry to register with.
+        connect.start_attempt(None)?;
+        Ok(connect)
+    }
+
+    /// Set the delay after which a new connection attempt is started if the
+    /// previous attempts haven't completed yet. Defaults to 250 milliseconds.
+    pub fn set_attempt_delay(&mut self, delay: Duration) {
+        self.attempt_delay = delay;
+    }
+
+    /// Returns the time until the next connection attempt should be started,
+    /// to be used as timeout in [`Poll::poll`].
+    ///
+    /// Returns `None` if there are no more addresses to attempt.
+    ///
+    /// [`Poll::poll`]: crate::Poll::poll
+    pub fn timeout(&self) -> Option<Duration> {
+        if self.addrs.is_empty() {
+            None
+        } else {
+            let next_attempt = self.last_attempt + self.attempt_delay;
+            Some(next_attempt.saturating_duration_since(Instant::now()))
+        }
+    }
+
+    /// Check the connection attempts, starting new attempts as needed.
+    ///
+    /// `registry` must be the registry `self` is registered with, new
+    /// connection attempts are registered with it using the token and
+    /// interests `self` was registered with.
+    ///
+    /// Returns `Ok(Some(stream))` once a connection is established, all other
+    /// connection attempts are closed. The returned stream remains registered
+    /// with the token and interests `self` was registered with.
+    ///
+    /// Returns `Ok(None)` if the connection attempts are still in progress, or
+    /// the error of the last attempt if all connection attempts failed.
+    pub fn try_connect(&mut self, registry: &Registry) -> io::Result<Option<TcpStream>> {
+        let mut failed = false;
+        let mut i = 0;
+        while i < self.attempts.len() {
+            match self.attempts[i].finish_connect() {
+                Ok(ConnectStatus::Connected) => {
+                    let stream = self.attempts.swap_remove(i);
+                    self.close_attempts(registry);
+                    self.addrs.clear();
+                    return Ok(Some(stream));
+                }
+                Ok(ConnectStatus::InProgress) => i += 1,
+                Err(err) => {
+                    let mut stream = self.attempts.swap_remove(i);
+                    if self.registration.is_some() {
+                        let _ = registry.deregister(&mut stream);
+                    }
+                    self.last_error = Some(err);
+                    failed = true;
+                }
+            }
+        }
+
+        if self.attempts.is_empty() {
+            // Start the next attempt right away, or fail if none are left.
+            self.start_attempt(Some(registry))?;
+        } else if !self.addrs.is_empty()
+            && (failed || self.last_attempt.elapsed() >= self.attempt_delay)
+        {
+            // A failed attempt is replaced right away, without waiting for the
+            // attempt delay. Failing to start an attempt is fine, as earlier
+            // attempts are still in progress.
+            let _ = self.start_attempt(Some(registry));
+        }
+        Ok(None)
+    }
+
+    /// Start a connection attempt to the next address, moving on to the next
+    /// address if starting the connection fails.
+    ///
+    /// Returns an error if no attempt could be started.
+    fn start_attempt(&mut self, registry: Option<&Registry>) -> io::Result<()> {
+        while let Some(addr) = self.addrs.pop_front() {
+            match self.connect(registry, addr) {
+                Ok(stream) => {
+                    self.attempts.push(stream);
+                    self.last_attempt = Instant::now();
+                    return Ok(());
+                }
+                Err(err) => self.last_error = Some(err),
+            }
+        }
+        Err(self.last_error.take().unwrap_or_else(|| {
+            io::Error::new(io::ErrorKind::NotConnected, "all connection attempts failed")
+        }))
+    }
+
+    fn connect(&self, registry: Option<&Registry>, addr: SocketAddr) -> io::Result<TcpStream> {
+        let mut stream = TcpStream::connect(addr)?;
+        if let (Some(registry), Some((token, interests))) = (registry, self.registration) {
+            registry.register(&mut stream, token, interests)?;
+        }
+        Ok(stream)
+    }
+
+    fn close_attempts(&mut self, registry: &Registry) {
+        for mut stream in self.attempts.drain(..) {
+            if self.registration.is_some() {
+                let _ = registry.deregister(&mut stream);
+            }
+        }
+    }
+}
+
+/// Sort `addrs` to alternate between address families, starting with the
+/// family of the first address, per section 4 of RFC 8305.
+fn interleave(addrs: Vec<SocketAddr>) -> VecDeque<SocketAddr> {
+    let first_is_ipv6 = match addrs.first() {
+        Some(addr) => addr.is_ipv6(),
+        None => return VecDeque::new(),
+    };
+    let (mut first, mut second): (VecDeque<_>, VecDeque<_>) = addrs
+        .into_iter()
+        .partition(|addr| addr.is_ipv6() == first_is_ipv6);
+    let mut sorted = VecDeque::with_capacity(first.len() + second.len());
+    loop {
+        match (first.pop_front(), second.pop_front()) {
+            (None, None) => return sorted,
+            (a, b) => sorted.extend(a.into_iter().chain(b)),
+        }
+    }
+}
+
+impl event::Source for TcpConnect {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        for i in 0..self.attempts.len() {
+            if let Err(err) = registry.register(&mut self.attempts[i], token, interests) {
+                // Undo the registrations that succeeded.
+                for stream in self.attempts[..i].iter_mut() {
+                    let _ = registry.deregister(stream);
+                }
+                return Err(err);
+            }
+        }
+        self.registration = Some((token, interests));
+        Ok(())
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        for i in 0..self.attempts.len() {
+            if let Err(err) = registry.reregister(&mut self.attempts[i], token, interests) {
+                // Restore the previous registration of the attempts that were
+                // already reregistered.
+                if let Some((token, interests)) = self.registration {
+                    for stream in self.attempts[..i].iter_mut() {
+                        let _ = registry.reregister(stream, token, interests);
+                    }
+                }
+                return Err(err);
+            }
+        }
+        self.registration = Some((token, interests));
+        Ok(())
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        // Deregister all attempts, even if one of them fails.
+        let mut result = Ok(());
+        for stream in self.attempts.iter_mut() {
+            if let Err(err) = registry.deregister(stream) {
+                result = result.and(Err(err));
+            }
+        }
+        self.registration = None;
+        result
+    }
+}
+
+impl fmt::Debug for TcpConnect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TcpConnect")
+            .field("addrs", &self.addrs)
+            .field("attempts", &self.attempts)
+            .field("attempt_delay", &self.attempt_delay)
+            .finish()
+    }
+}
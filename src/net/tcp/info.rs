@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+/// Information about a TCP connection, as returned by
+/// [`TcpStream::tcp_info`].
+///
+/// This is a subset of the information in the `TCP_INFO` socket option.
+///
+/// [`TcpStream::tcp_info`]: crate::net::TcpStream::tcp_info
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TcpInfo {
+    pub(crate) rtt: Duration,
+    pub(crate) rtt_var: Duration,
+    pub(crate) min_rtt: Option<Duration>,
+    pub(crate) retransmits: u8,
+    pub(crate) total_retransmits: u32,
+    pub(crate) congestion_window: u32,
+    pub(crate) send_mss: u32,
+    pub(crate) delivery_rate: Option<u64>,
+}
+
+impl TcpInfo {
+    /// Returns the smoothed round trip time of the connection.
+    pub fn rtt(&self) -> Duration {
+        self.rtt
+    }
+
+    /// Returns the variance of the round trip time, see [`TcpInfo::rtt`].
+    pub fn rtt_var(&self) -> Duration {
+        self.rtt_var
+    }
+
+    /// Returns the minimum round trip time observed on the connection.
+    ///
+    /// Returns `None` if the kernel doesn't report it (before Linux 4.6).
+    pub fn min_rtt(&self) -> Option<Duration> {
+        self.min_rtt
+    }
+
+    /// Returns the number of retransmissions of the oldest unacknowledged
+    /// segment, i.e. the number of consecutive timeouts.
+    pub fn retransmits(&self) -> u8 {
+        self.retransmits
+    }
+
+    /// Returns the total number of segments retransmitted over the lifetime of
+    /// the connection.
+    pub fn total_retransmits(&self) -> u32 {
+        self.total_retransmits
+    }
+
+    /// Returns the sending congestion window, in segments.
+    pub fn congestion_window(&self) -> u32 {
+        self.congestion_window
+    }
+
+    /// Returns the maximum segment size used for sending, in bytes.
+    pub fn send_mss(&self) -> u32 {
+        self.send_mss
+    }
+
+    /// Returns the most recent delivery rate of the connection, in bytes per
+    /// second.
+    ///
+    /// Returns `None` if the kernel doesn't report it (before Linux 4.9).
+    pub fn delivery_rate(&self) -> Option<u64> {
+        self.delivery_rate
+    }
+}
@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+/// Configures a socket's TCP keepalive parameters, see
+/// [`TcpStream::set_keepalive_params`].
+///
+/// Parameters that are not set keep the value currently set on the socket,
+/// which defaults to the value configured in the OS.
+///
+/// This is only available on platforms that support configuring the
+/// parameters per socket, on other platforms use [`TcpStream::set_keepalive`]
+/// to enable keepalive with the parameters configured in the OS.
+///
+/// [`TcpStream::set_keepalive_params`]: crate::net::TcpStream::set_keepalive_params
+/// [`TcpStream::set_keepalive`]: crate::net::TcpStream::set_keepalive
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::TcpKeepalive;
+/// use std::time::Duration;
+///
+/// let keepalive = TcpKeepalive::new()
+///     .with_time(Duration::from_secs(60))
+///     .with_interval(Duration::from_secs(10))
+///     .with_retries(5);
+/// # let _ = keepalive;
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TcpKeepalive {
+    pub(crate) time: Option<Duration>,
+    pub(crate) interval: Option<Duration>,
+    pub(crate) retries: Option<u32>,
+}
+
+impl TcpKeepalive {
+    /// Returns a new, empty set of TCP keepalive parameters.
+    pub fn new() -> TcpKeepalive {
+        TcpKeepalive::default()
+    }
+
+    /// Set the amount of time after which TCP keepalive probes will be sent
+    /// on idle connections.
+    ///
+    /// This sets `TCP_KEEPIDLE` (`TCP_KEEPALIVE` on Apple platforms). The
+    /// value is rounded up to whole seconds.
+    pub fn with_time(self, time: Duration) -> TcpKeepalive {
+        TcpKeepalive {
+            time: Some(time),
+            ..self
+        }
+    }
+
+    /// Set the time interval between TCP keepalive probes.
+    ///
+    /// This sets `TCP_KEEPINTVL`. The value is rounded up to whole seconds.
+    pub fn with_interval(self, interval: Duration) -> TcpKeepalive {
+        TcpKeepalive {
+            interval: Some(interval),
+            ..self
+        }
+    }
+
+    /// Set the number of unacknowledged TCP keepalive probes that will be
+    /// sent before the connection is dropped.
+    ///
+    /// This sets `TCP_KEEPCNT`.
+    pub fn with_retries(self, retries: u32) -> TcpKeepalive {
+        TcpKeepalive {
+            retries: Some(retries),
+            ..self
+        }
+    }
+}
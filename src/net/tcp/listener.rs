@@ -5,14 +5,17 @@ use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use std::os::wasi::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 #[cfg(windows)]
 use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+#[cfg(unix)]
+use std::time::Duration;
 use std::{fmt, io};
 
 use crate::io_source::IoSource;
-use crate::net::TcpStream;
+use crate::net::accept::accept_many;
 #[cfg(unix)]
-use crate::sys::tcp::set_reuseaddr;
+use crate::net::accept::{accept_or_shed, FdReserve};
 #[cfg(not(target_os = "wasi"))]
-use crate::sys::tcp::{bind, listen, new_for_addr};
+use crate::net::TcpSocket;
+use crate::net::TcpStream;
 use crate::{event, sys, Interest, Registry, Token};
 
 /// A structure representing a socket server
@@ -55,13 +58,11 @@ impl TcpListener {
     /// 2. Set the `SO_REUSEADDR` option on the socket on Unix.
     /// 3. Bind the socket to the specified address.
     /// 4. Calls `listen` on the socket to prepare it to receive new connections.
+    ///
+    /// Use [`TcpSocket`] to set other options before binding and listening.
     #[cfg(not(target_os = "wasi"))]
     pub fn bind(addr: SocketAddr) -> io::Result<TcpListener> {
-        let socket = new_for_addr(addr)?;
-        #[cfg(unix)]
-        let listener = unsafe { TcpListener::from_raw_fd(socket) };
-        #[cfg(windows)]
-        let listener = unsafe { TcpListener::from_raw_socket(socket as _) };
+        let socket = TcpSocket::new_for_addr(addr)?;
 
         // On platforms with Berkeley-derived sockets, this allows to quickly
         // rebind a socket, without needing to wait for the OS to clean up the
@@ -71,11 +72,10 @@ impl TcpListener {
         // which allows “socket hijacking”, so we explicitly don't set it here.
         // https://docs.microsoft.com/en-us/windows/win32/winsock/using-so-reuseaddr-and-so-exclusiveaddruse
         #[cfg(not(windows))]
-        set_reuseaddr(&listener.inner, true)?;
+        socket.set_reuseaddr(true)?;
 
-        bind(&listener.inner, addr)?;
-        listen(&listener.inner, 1024)?;
-        Ok(listener)
+        socket.bind(addr)?;
+        socket.listen(1024)
     }
 
     /// Creates a new `TcpListener` from a standard `net::TcpListener`.
@@ -104,6 +104,67 @@ impl TcpListener {
         })
     }
 
+    /// Accepts up to `max` new connections, adding them to `accepted`.
+    ///
+    /// This calls [`accept`] until it returns a [`WouldBlock`] error, or `max`
+    /// connections are accepted. Returns the number of connections accepted.
+    /// If this is `max` there may be more connections pending, call
+    /// `accept_many` again before waiting for the next event.
+    ///
+    /// Connections that were aborted by the peer before they were accepted
+    /// (`ECONNABORTED`) are skipped. If no connections are pending this returns
+    /// a [`WouldBlock`] error. If another error occurs after accepting one or
+    /// more connections the accepted connections are returned and the error is
+    /// dropped. Errors that persist, such as running out of file descriptors,
+    /// are returned by the next call.
+    ///
+    /// [`accept`]: TcpListener::accept
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn accept_many(
+        &self,
+        accepted: &mut Vec<(TcpStream, SocketAddr)>,
+        max: usize,
+    ) -> io::Result<usize> {
+        accept_many(|| self.accept(), accepted, max)
+    }
+
+    /// Accepts a new connection, shedding the pending connection if the
+    /// process ran out of file descriptors.
+    ///
+    /// This works like [`accept`], but if accepting fails because no more
+    /// file descriptors are available it uses the file descriptor reserved in
+    /// `reserve` to accept the connection and close it right away. In that
+    /// case `Ok(None)` is returned, after which the listener can continue
+    /// accepting connections. If no connection is pending a [`WouldBlock`]
+    /// error is returned, as with `accept`.
+    ///
+    /// If the connection can't be shed, e.g. because `reserve` doesn't hold
+    /// a file descriptor, the original error is returned, which can be
+    /// checked using [`FdReserve::is_exhausted`]. Any error hit while shedding
+    /// the connection is returned as well.
+    ///
+    /// See [`FdReserve`] for an example.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    ///
+    /// [`accept`]: TcpListener::accept
+    #[cfg(unix)]
+    pub fn accept_or_shed(
+        &self,
+        reserve: &mut FdReserve,
+    ) -> io::Result<Option<(TcpStream, SocketAddr)>> {
+        accept_or_shed(
+            || self.accept(),
+            reserve,
+            |(stream, _), reset| {
+                if reset {
+                    stream.set_linger(Some(Duration::from_secs(0)))?;
+                }
+                Ok(())
+            },
+        )
+    }
+
     /// Returns the local socket address of this listener.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         self.inner.local_addr()
@@ -126,6 +187,46 @@ impl TcpListener {
         self.inner.ttl()
     }
 
+    /// Sets the value of the `TCP_FASTOPEN` option on this socket, enabling
+    /// TCP Fast Open with a queue of `queue_len` pending Fast Open requests.
+    ///
+    /// Server side Fast Open must be enabled in the `net.ipv4.tcp_fastopen`
+    /// sysctl. Setting `queue_len` to zero disables Fast Open.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_fastopen(&self, queue_len: u32) -> io::Result<()> {
+        sys::tcp::set_fastopen(self.sys(), queue_len)
+    }
+
+    /// Get the value of the `TCP_FASTOPEN` option on this socket.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn fastopen(&self) -> io::Result<u32> {
+        sys::tcp::get_fastopen(self.sys())
+    }
+
+    /// Sets the value of the `TCP_DEFER_ACCEPT` option on this socket.
+    ///
+    /// If set, accepting a connection is delayed until data arrives on it,
+    /// or until `timeout` expires. The timeout is rounded to the number of
+    /// SYN-ACK retransmissions that fit in it, which is reflected in the
+    /// value returned by [`defer_accept`]. A zero timeout disables the
+    /// option.
+    ///
+    /// [`defer_accept`]: TcpListener::defer_accept
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_defer_accept(&self, timeout: Duration) -> io::Result<()> {
+        sys::tcp::set_defer_accept(self.sys(), timeout)
+    }
+
+    /// Get the value of the `TCP_DEFER_ACCEPT` option on this socket.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn defer_accept(&self) -> io::Result<Duration> {
+        sys::tcp::get_defer_accept(self.sys())
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -134,6 +235,16 @@ impl TcpListener {
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
+
+    /// Returns the socket for use with the `sys::tcp` functions.
+    #[cfg(not(target_os = "wasi"))]
+    pub(crate) fn sys(&self) -> sys::tcp::TcpSocket {
+        #[cfg(unix)]
+        let socket = self.inner.as_raw_fd();
+        #[cfg(windows)]
+        let socket = self.inner.as_raw_socket() as _;
+        socket
+    }
 }
 
 impl event::Source for TcpListener {
@@ -158,6 +269,14 @@ impl event::Source for TcpListener {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.pause(registry)
+    }
+
+    fn resume(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.resume(registry)
+    }
 }
 
 impl fmt::Debug for TcpListener {
@@ -1,5 +1,25 @@
 mod listener;
 pub use self::listener::TcpListener;
 
+cfg_tcp_keepalive! {
+    mod keepalive;
+    pub use self::keepalive::TcpKeepalive;
+}
+
+#[cfg(not(target_os = "wasi"))]
+mod connect;
+#[cfg(not(target_os = "wasi"))]
+pub use self::connect::TcpConnect;
+
+#[cfg(target_os = "linux")]
+mod info;
+#[cfg(target_os = "linux")]
+pub use self::info::TcpInfo;
+
+#[cfg(not(target_os = "wasi"))]
+mod socket;
+#[cfg(not(target_os = "wasi"))]
+pub use self::socket::TcpSocket;
+
 mod stream;
 pub use self::stream::TcpStream;
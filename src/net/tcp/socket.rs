@@ -0,0 +1,381 @@
+use std::io;
+use std::mem;
+use std::net::SocketAddr;
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+#[cfg(windows)]
+use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+
+use crate::net::{TcpListener, TcpStream};
+use crate::sys;
+
+/// A non-blocking TCP socket used to configure a stream or listener before it
+/// is connected or starts listening.
+///
+/// [`TcpListener::bind`] and [`TcpStream::connect`] create, configure and
+/// bind or connect a socket in a single call. `TcpSocket` gives access to the
+/// socket in between, so that options that must be set before binding,
+/// listening or connecting can be set, e.g. `SO_REUSEPORT` or the buffer
+/// sizes. A local address can be bound before connecting using [`bind`].
+///
+/// The socket will be closed when the value is dropped.
+///
+/// [`bind`]: TcpSocket::bind
+///
+/// # Examples
+///
+/// Creating a listener.
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::TcpSocket;
+///
+/// let addr = "127.0.0.1:0".parse()?;
+/// let socket = TcpSocket::new_v4()?;
+/// socket.set_reuseaddr(true)?;
+/// socket.set_recv_buffer_size(64 * 1024)?;
+/// socket.bind(addr)?;
+///
+/// let listener = socket.listen(128)?;
+/// #     drop(listener);
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// Connecting a stream from a specific local address.
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::TcpSocket;
+///
+/// # let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+/// # let addr = listener.local_addr()?;
+/// let socket = TcpSocket::new_v4()?;
+/// socket.bind("127.0.0.1:0".parse()?)?;
+///
+/// let stream = socket.connect(addr)?;
+/// #     drop(stream);
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TcpSocket {
+    sys: sys::tcp::TcpSocket,
+}
+
+impl TcpSocket {
+    /// Create a new IPv4 TCP socket.
+    ///
+    /// This calls `socket(2)`.
+    pub fn new_v4() -> io::Result<TcpSocket> {
+        TcpSocket::new_for_addr(SocketAddr::from(([0, 0, 0, 0], 0)))
+    }
+
+    /// Create a new IPv6 TCP socket.
+    ///
+    /// This calls `socket(2)`.
+    pub fn new_v6() -> io::Result<TcpSocket> {
+        TcpSocket::new_for_addr(SocketAddr::from(([0; 16], 0)))
+    }
+
+    /// Create a new TCP socket for the address family of `addr`.
+    ///
+    /// This calls `socket(2)`, the socket is not bound to `addr`.
+    pub fn new_for_addr(addr: SocketAddr) -> io::Result<TcpSocket> {
+        sys::tcp::new_for_addr(addr).map(|sys| TcpSocket { sys })
+    }
+
+    /// Bind `addr` to the TCP socket.
+    ///
+    /// This can be used before [`connect`] to select the local address of the
+    /// connection, or before [`listen`] to select the address to listen on.
+    ///
+    /// [`connect`]: TcpSocket::connect
+    /// [`listen`]: TcpSocket::listen
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        sys::tcp::bind(self.sys, addr)
+    }
+
+    /// Connect the socket to `addr`.
+    ///
+    /// This consumes the socket and performs the connect operation. Once the
+    /// connection completes, the socket is now a non-blocking `TcpStream` and
+    /// can be used as such. See [`TcpStream::connect`] for how to determine
+    /// if the connection completed.
+    pub fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        let stream = self.into_stream();
+        sys::tcp::connect(stream.sys(), addr)?;
+        Ok(stream)
+    }
+
+    /// Connect the socket to `addr` using TCP Fast Open, sending `data` along
+    /// with the SYN packet.
+    ///
+    /// This works like [`connect`], but if a Fast Open cookie for `addr` is
+    /// cached (from a previous connection) `data` is sent with the SYN,
+    /// saving a round trip. Returns the stream and the number of bytes of
+    /// `data` that were sent. This may be less than `data.len()`, or zero if
+    /// no cookie was available, in which case the remaining bytes must be
+    /// written once the connection is established.
+    ///
+    /// Client side Fast Open must be enabled in the
+    /// `net.ipv4.tcp_fastopen` sysctl, it is by default.
+    ///
+    /// [`connect`]: TcpSocket::connect
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn connect_with_data(
+        self,
+        addr: SocketAddr,
+        data: &[u8],
+    ) -> io::Result<(TcpStream, usize)> {
+        let stream = self.into_stream();
+        let n = sys::tcp::connect_with_data(stream.sys(), addr, data)?;
+        Ok((stream, n))
+    }
+
+    /// Listen for inbound connections, converting the socket to a
+    /// `TcpListener`.
+    ///
+    /// `backlog` is the maximum number of pending connections, values larger
+    /// than the OS allows are capped by the OS.
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        let listener = self.into_listener();
+        sys::tcp::listen(listener.sys(), backlog)?;
+        Ok(listener)
+    }
+
+    /// Sets the value of `SO_REUSEADDR` on this socket.
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        sys::tcp::set_reuseaddr(self.sys, reuseaddr)
+    }
+
+    /// Get the value of `SO_REUSEADDR` set on this socket.
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        sys::tcp::get_reuseaddr(self.sys)
+    }
+
+    /// Sets the value of `SO_REUSEPORT` on this socket.
+    ///
+    /// This allows multiple sockets to bind the same address and port, e.g.
+    /// to have a listener per thread. It must be set on every socket before
+    /// binding.
+    #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos")))))
+    )]
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        sys::tcp::set_reuseport(self.sys, reuseport)
+    }
+
+    /// Get the value of `SO_REUSEPORT` set on this socket.
+    #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos")))))
+    )]
+    pub fn reuseport(&self) -> io::Result<bool> {
+        sys::tcp::get_reuseport(self.sys)
+    }
+
+    /// Sets the value of `SO_SNDBUF` on this socket.
+    pub fn set_send_buffer_size(&self, size: u32) -> io::Result<()> {
+        sys::tcp::set_send_buffer_size(self.sys, size)
+    }
+
+    /// Get the value of `SO_SNDBUF` set on this socket.
+    ///
+    /// Note that if [`set_send_buffer_size`] has been called on this socket
+    /// previously, the value returned by this function may not be the same as
+    /// the argument provided to `set_send_buffer_size`. This is for the
+    /// following reasons:
+    ///
+    /// * Most operating systems have minimum and maximum allowed sizes for the
+    ///   send buffer, and will clamp the provided value if it is below the
+    ///   minimum or above the maximum.
+    /// * Linux will double the buffer size to account for internal bookkeeping
+    ///   data.
+    ///
+    /// [`set_send_buffer_size`]: TcpSocket::set_send_buffer_size
+    pub fn send_buffer_size(&self) -> io::Result<u32> {
+        sys::tcp::get_send_buffer_size(self.sys)
+    }
+
+    /// Sets the value of `SO_RCVBUF` on this socket.
+    ///
+    /// To take effect for the TCP window scaling of a connection this must be
+    /// set before connecting, or before listening for accepted connections.
+    pub fn set_recv_buffer_size(&self, size: u32) -> io::Result<()> {
+        sys::tcp::set_recv_buffer_size(self.sys, size)
+    }
+
+    /// Get the value of `SO_RCVBUF` set on this socket.
+    ///
+    /// Note that if [`set_recv_buffer_size`] has been called on this socket
+    /// previously, the value returned by this function may not be the same as
+    /// the argument provided to `set_recv_buffer_size`, for the same reasons
+    /// as described in [`send_buffer_size`].
+    ///
+    /// [`set_recv_buffer_size`]: TcpSocket::set_recv_buffer_size
+    /// [`send_buffer_size`]: TcpSocket::send_buffer_size
+    pub fn recv_buffer_size(&self) -> io::Result<u32> {
+        sys::tcp::get_recv_buffer_size(self.sys)
+    }
+
+    /// Sets the value of `IPV6_V6ONLY` on this socket.
+    ///
+    /// If this is set to `true` the socket is restricted to IPv6
+    /// communication only, otherwise a socket bound to an unspecified IPv6
+    /// address also accepts IPv4 connections using IPv4-mapped addresses.
+    /// Only valid for IPv6 sockets and must be set before binding.
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        sys::tcp::set_only_v6(self.sys, only_v6)
+    }
+
+    /// Get the value of `IPV6_V6ONLY` set on this socket.
+    pub fn only_v6(&self) -> io::Result<bool> {
+        sys::tcp::get_only_v6(self.sys)
+    }
+
+    /// Sets the value of `IP_FREEBIND` on this socket.
+    ///
+    /// This allows binding to an address that is not (yet) assigned to any
+    /// local interface.
+    #[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux")))
+    )]
+    pub fn set_freebind(&self, freebind: bool) -> io::Result<()> {
+        sys::tcp::set_freebind(self.sys, freebind)
+    }
+
+    /// Get the value of `IP_FREEBIND` set on this socket.
+    #[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux")))
+    )]
+    pub fn freebind(&self) -> io::Result<bool> {
+        sys::tcp::get_freebind(self.sys)
+    }
+
+    /// Sets the value of `IP_BIND_ADDRESS_NO_PORT` on this socket.
+    ///
+    /// When binding a socket with port 0 before connecting, this delays the
+    /// selection of the local port until [`connect`], so the same port can be
+    /// used for connections to different destinations. This avoids
+    /// exhausting the ephemeral ports when binding many outgoing connections.
+    ///
+    /// [`connect`]: TcpSocket::connect
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_bind_address_no_port(&self, no_port: bool) -> io::Result<()> {
+        sys::tcp::set_bind_address_no_port(self.sys, no_port)
+    }
+
+    /// Get the value of `IP_BIND_ADDRESS_NO_PORT` set on this socket.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn bind_address_no_port(&self) -> io::Result<bool> {
+        sys::tcp::get_bind_address_no_port(self.sys)
+    }
+
+    /// Returns the local address of this socket.
+    ///
+    /// Will return `Err` result in windows if called before calling `bind`.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        sys::tcp::local_addr(self.sys)
+    }
+
+    fn into_stream(self) -> TcpStream {
+        let sys = self.sys;
+        // The stream takes ownership of the socket.
+        mem::forget(self);
+        #[cfg(unix)]
+        let stream = unsafe { TcpStream::from_raw_fd(sys) };
+        #[cfg(windows)]
+        let stream = unsafe { TcpStream::from_raw_socket(sys as _) };
+        stream
+    }
+
+    fn into_listener(self) -> TcpListener {
+        let sys = self.sys;
+        // The listener takes ownership of the socket.
+        mem::forget(self);
+        #[cfg(unix)]
+        let listener = unsafe { TcpListener::from_raw_fd(sys) };
+        #[cfg(windows)]
+        let listener = unsafe { TcpListener::from_raw_socket(sys as _) };
+        listener
+    }
+}
+
+impl Drop for TcpSocket {
+    fn drop(&mut self) {
+        sys::tcp::close(self.sys);
+    }
+}
+
+#[cfg(unix)]
+impl IntoRawFd for TcpSocket {
+    fn into_raw_fd(self) -> RawFd {
+        let fd = self.sys;
+        mem::forget(self);
+        fd
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for TcpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.sys
+    }
+}
+
+#[cfg(unix)]
+impl FromRawFd for TcpSocket {
+    /// Converts a `RawFd` to a `TcpSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> TcpSocket {
+        TcpSocket { sys: fd }
+    }
+}
+
+#[cfg(windows)]
+impl IntoRawSocket for TcpSocket {
+    fn into_raw_socket(self) -> RawSocket {
+        let socket = self.sys;
+        mem::forget(self);
+        socket as RawSocket
+    }
+}
+
+#[cfg(windows)]
+impl AsRawSocket for TcpSocket {
+    fn as_raw_socket(&self) -> RawSocket {
+        self.sys as RawSocket
+    }
+}
+
+#[cfg(windows)]
+impl FromRawSocket for TcpSocket {
+    /// Converts a `RawSocket` to a `TcpSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    unsafe fn from_raw_socket(socket: RawSocket) -> TcpSocket {
+        TcpSocket { sys: socket as _ }
+    }
+}
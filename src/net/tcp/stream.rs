@@ -7,11 +7,30 @@ use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use std::os::wasi::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 #[cfg(windows)]
 use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+#[cfg(not(target_os = "wasi"))]
+use std::time::Duration;
 
 use crate::io_source::IoSource;
+use crate::net::connect_status::finish_connect;
+use crate::net::ConnectStatus;
+#[cfg(unix)]
+use crate::net::MsgFlags;
+#[cfg(target_os = "linux")]
+use crate::net::TcpInfo;
+#[cfg(target_os = "linux")]
+use crate::net::ZeroCopyCompletion;
 #[cfg(not(target_os = "wasi"))]
-use crate::sys::tcp::{connect, new_for_addr};
-use crate::{event, Interest, Registry, Token};
+use crate::net::TcpSocket;
+#[cfg(all(feature = "os-ext", any(target_os = "android", target_os = "linux")))]
+use crate::unix::pipe;
+use crate::{event, sys, Interest, Registry, Token};
+
+cfg_send_file! {
+    use std::fs::File;
+}
+cfg_tcp_keepalive! {
+    use crate::net::TcpKeepalive;
+}
 
 /// A non-blocking TCP stream between a local socket and a remote socket.
 ///
@@ -60,35 +79,62 @@ impl TcpStream {
     /// *non-blocking* connect it will not block the thread and instead return
     /// an unconnected `TcpStream`.
     ///
-    /// Ensuring the returned stream is connected is surprisingly complex when
-    /// considering cross-platform support. Doing this properly should follow
-    /// the steps below, an example implementation can be found
-    /// [here](https://github.com/Thomasdezeeuw/heph/blob/0c4f1ab3eaf08bea1d65776528bfd6114c9f8374/src/net/tcp/stream.rs#L560-L622).
+    /// To ensure the returned stream is connected follow the steps below.
     ///
     ///  1. Call `TcpStream::connect`
     ///  2. Register the returned stream with at least [write interest].
     ///  3. Wait for a (writable) event.
-    ///  4. Check `TcpStream::peer_addr`. If it returns `libc::EINPROGRESS` or
-    ///     `ErrorKind::NotConnected` it means the stream is not yet connected,
-    ///     go back to step 3. If it returns an address it means the stream is
-    ///     connected, go to step 5. If another error is returned something
-    ///     whent wrong.
+    ///  4. Call [`TcpStream::finish_connect`]. If it returns
+    ///     [`ConnectStatus::InProgress`] the stream is not yet connected, go
+    ///     back to step 3. If it returns [`ConnectStatus::Connected`] the
+    ///     stream is connected, go to step 5. If an error is returned the
+    ///     connect failed.
     ///  5. Now the stream can be used.
     ///
     /// This may return a `WouldBlock` in which case the socket connection
     /// cannot be completed immediately, it usually means there are insufficient
     /// entries in the routing cache.
     ///
+    /// Use [`TcpSocket`] to set options, or bind a local address, before
+    /// connecting.
+    ///
     /// [write interest]: Interest::WRITABLE
     #[cfg(not(target_os = "wasi"))]
     pub fn connect(addr: SocketAddr) -> io::Result<TcpStream> {
-        let socket = new_for_addr(addr)?;
-        #[cfg(unix)]
-        let stream = unsafe { TcpStream::from_raw_fd(socket) };
-        #[cfg(windows)]
-        let stream = unsafe { TcpStream::from_raw_socket(socket as _) };
-        connect(&stream.inner, addr)?;
-        Ok(stream)
+        TcpSocket::new_for_addr(addr)?.connect(addr)
+    }
+
+    /// Create a new TCP stream and issue a non-blocking connect to the
+    /// specified address using TCP Fast Open, sending `data` along with the
+    /// SYN packet.
+    ///
+    /// Returns the stream and the number of bytes of `data` that were sent,
+    /// this may be zero. Determining whether the connection is established
+    /// works the same as for [`TcpStream::connect`]. See
+    /// [`TcpSocket::connect_with_data`] for details.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::TcpStream;
+    ///
+    /// # let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    /// # let addr = listener.local_addr()?;
+    /// let request = b"GET / HTTP/1.1\r\n\r\n";
+    /// let (stream, sent) = TcpStream::connect_with_data(addr, request)?;
+    /// // Write the remainder once the stream is connected.
+    /// let remaining = &request[sent..];
+    /// # drop((stream, remaining));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn connect_with_data(addr: SocketAddr, data: &[u8]) -> io::Result<(TcpStream, usize)> {
+        TcpSocket::new_for_addr(addr)?.connect_with_data(addr, data)
     }
 
     /// Creates a new `TcpStream` from a standard `net::TcpStream`.
@@ -119,6 +165,50 @@ impl TcpStream {
         self.inner.local_addr()
     }
 
+    /// Check whether a non-blocking connect, started by
+    /// [`TcpStream::connect`], has completed.
+    ///
+    /// Call this after receiving a (writable) event. Returns
+    /// [`ConnectStatus::Connected`] once the connection is established,
+    /// [`ConnectStatus::InProgress`] if it isn't established yet, or the error
+    /// of the connect if it failed.
+    ///
+    /// This uses the `SO_ERROR` option to retrieve the connect error, so
+    /// don't call [`TcpStream::take_error`] while connecting, as the error
+    /// would be lost.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::{ConnectStatus, TcpStream};
+    /// use mio::{Events, Interest, Poll, Token};
+    ///
+    /// # let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    /// # let addr = listener.local_addr()?;
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(128);
+    ///
+    /// let mut stream = TcpStream::connect(addr)?;
+    /// poll.registry()
+    ///     .register(&mut stream, Token(0), Interest::WRITABLE)?;
+    ///
+    /// loop {
+    ///     poll.poll(&mut events, None)?;
+    ///     if stream.finish_connect()? == ConnectStatus::Connected {
+    ///         break;
+    ///     }
+    /// }
+    /// // Now the stream can be used.
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn finish_connect(&self) -> io::Result<ConnectStatus> {
+        finish_connect(self.take_error(), || self.peer_addr())
+    }
+
     /// Shuts down the read, write, or both halves of this connection.
     ///
     /// This function will cause all pending and future I/O on the specified
@@ -128,6 +218,142 @@ impl TcpStream {
         self.inner.shutdown(how)
     }
 
+    cfg_send_file! {
+        /// Send up to `len` bytes of `file`, starting at `offset`, to the stream
+        /// without copying the data through user space, using `sendfile(2)`.
+        ///
+        /// Returns the number of bytes sent, which may be less than `len`, or zero
+        /// if `offset` is at or past the end of the file. The file's own offset is
+        /// not changed.
+        ///
+        /// If the stream's send buffer is full this returns a [`WouldBlock`]
+        /// error, wait for a writable event before trying again. As with
+        /// [`write`], call `send_file` until it returns `WouldBlock` (or all
+        /// data is sent) before waiting for the next event.
+        ///
+        /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+        /// [`write`]: Write::write
+        pub fn send_file(&self, file: &File, offset: u64, len: usize) -> io::Result<usize> {
+            self.inner
+                .do_io(|inner| sys::tcp::send_file(inner.as_raw_fd(), file, offset, len))
+        }
+    }
+
+    /// Move up to `len` bytes from the stream into `pipe` without copying
+    /// the data through user space, using `splice(2)`.
+    ///
+    /// Returns the number of bytes moved, zero if the stream was shut down
+    /// for reading. Returns a [`WouldBlock`] error if either no data is
+    /// available on the stream or `pipe` is full, wait for a readable event on
+    /// the stream or a writable event on `pipe` respectively.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    #[cfg(all(feature = "os-ext", any(target_os = "android", target_os = "linux")))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(feature = "os-ext", any(target_os = "android", target_os = "linux"))))
+    )]
+    pub fn splice_to(&self, pipe: &pipe::Sender, len: usize) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::tcp::splice(inner.as_raw_fd(), pipe.as_raw_fd(), len))
+    }
+
+    /// Move up to `len` bytes from `pipe` into the stream without copying the
+    /// data through user space, using `splice(2)`.
+    ///
+    /// Returns the number of bytes moved, zero if the sending end of `pipe`
+    /// was closed and no data is left. Returns a [`WouldBlock`] error if either
+    /// `pipe` is empty or the stream's send buffer is full, wait for a
+    /// readable event on `pipe` or a writable event on the stream
+    /// respectively.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    #[cfg(all(feature = "os-ext", any(target_os = "android", target_os = "linux")))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(feature = "os-ext", any(target_os = "android", target_os = "linux"))))
+    )]
+    pub fn splice_from(&self, pipe: &pipe::Receiver, len: usize) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::tcp::splice(pipe.as_raw_fd(), inner.as_raw_fd(), len))
+    }
+
+    /// Sets the value of the `SO_ZEROCOPY` option on this socket.
+    ///
+    /// This must be enabled before using [`send_zerocopy`].
+    ///
+    /// [`send_zerocopy`]: TcpStream::send_zerocopy
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_zerocopy(&self, zerocopy: bool) -> io::Result<()> {
+        sys::tcp::set_zerocopy(self.inner.as_raw_fd(), zerocopy)
+    }
+
+    /// Get the value of the `SO_ZEROCOPY` option on this socket.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn zerocopy(&self) -> io::Result<bool> {
+        sys::tcp::get_zerocopy(self.inner.as_raw_fd())
+    }
+
+    /// Send data using `MSG_ZEROCOPY`, avoiding copying `buf` into the kernel.
+    ///
+    /// Returns the number of bytes sent, like [`write`](Write::write).
+    ///
+    /// Unlike a regular send the kernel keeps using `buf` after this returns,
+    /// so `buf` must not be modified or dropped until the kernel signals the
+    /// completion of the send. Completions are signaled with an error event
+    /// ([`Event::is_error`]) for the registration of this socket, after which
+    /// they can be read using [`recv_zerocopy_completion`]. The error event is
+    /// generated regardless of the interests the socket is registered with.
+    ///
+    /// [`set_zerocopy`] must be enabled first, otherwise this returns an
+    /// error.
+    ///
+    /// # Notes
+    ///
+    /// **A completion looks like an error on the socket.** On Linux the
+    /// completion event is `EPOLLERR`, if the socket is also writable the
+    /// event returns true for [`Event::is_write_closed`] as well, even though
+    /// the socket is still open. An event loop using zerocopy sends must not
+    /// close the socket on such an event. Instead it should read all
+    /// completions using [`recv_zerocopy_completion`], until it returns a
+    /// [`WouldBlock`] error, and then use [`take_error`] to check for an
+    /// actual error.
+    ///
+    /// [`Event::is_error`]: crate::event::Event::is_error
+    /// [`Event::is_write_closed`]: crate::event::Event::is_write_closed
+    /// [`recv_zerocopy_completion`]: TcpStream::recv_zerocopy_completion
+    /// [`set_zerocopy`]: TcpStream::set_zerocopy
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [`take_error`]: TcpStream::take_error
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn send_zerocopy(&self, buf: &[u8]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::tcp::send_zerocopy(inner.as_raw_fd(), buf))
+    }
+
+    /// Receive the next zerocopy completion from the socket's error queue.
+    ///
+    /// Returns a [`WouldBlock`] error if there are no pending completions. See
+    /// [`send_zerocopy`].
+    ///
+    /// If the next message on the error queue is not a zerocopy completion,
+    /// e.g. an ICMP error when `IP_RECVERR` is enabled, the message is removed
+    /// from the queue and the error it carries is returned. Messages that
+    /// can't be parsed are returned as an [`InvalidData`] error.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [`InvalidData`]: io::ErrorKind::InvalidData
+    /// [`send_zerocopy`]: TcpStream::send_zerocopy
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn recv_zerocopy_completion(&self) -> io::Result<ZeroCopyCompletion> {
+        self.inner
+            .do_io(|inner| sys::tcp::recv_zerocopy_completion(inner.as_raw_fd()))
+    }
+
     /// Sets the value of the `TCP_NODELAY` option on this socket.
     ///
     /// If set, this option disables the Nagle algorithm. This means that
@@ -189,6 +415,271 @@ impl TcpStream {
         self.inner.ttl()
     }
 
+    /// Sets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// If enabled TCP keepalive probes are sent on idle connections, using
+    /// the parameters configured in the OS. Use [`set_keepalive_params`] to
+    /// set the parameters for this socket.
+    ///
+    /// [`set_keepalive_params`]: TcpStream::set_keepalive_params
+    #[cfg(not(target_os = "wasi"))]
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        sys::tcp::set_keepalive(self.sys(), keepalive)
+    }
+
+    /// Returns whether or not TCP keepalive is enabled on this socket, i.e.
+    /// the value of the `SO_KEEPALIVE` option.
+    #[cfg(not(target_os = "wasi"))]
+    pub fn keepalive(&self) -> io::Result<bool> {
+        sys::tcp::get_keepalive(self.sys())
+    }
+
+    cfg_tcp_keepalive! {
+        /// Enables TCP keepalive on this socket, setting the parameters in
+        /// `keepalive`.
+        ///
+        /// Parameters not set in `keepalive` keep their current value.
+        ///
+        /// The parameters are set one at a time. If the OS rejects one of
+        /// them an error is returned, but the parameters set before it keep
+        /// their new value and keepalive is not enabled.
+        ///
+        /// # Examples
+        ///
+        #[cfg_attr(feature = "os-poll", doc = "```")]
+        #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+        /// # use std::error::Error;
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// use mio::net::{TcpKeepalive, TcpStream};
+        /// use std::time::Duration;
+        ///
+        /// # let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        /// # let addr = listener.local_addr()?;
+        /// let stream = TcpStream::connect(addr)?;
+        ///
+        /// let keepalive = TcpKeepalive::new()
+        ///     .with_time(Duration::from_secs(60))
+        ///     .with_interval(Duration::from_secs(10))
+        ///     .with_retries(5);
+        /// stream.set_keepalive_params(keepalive)?;
+        ///
+        /// assert!(stream.keepalive()?);
+        /// assert_eq!(stream.keepalive_time()?, Duration::from_secs(60));
+        /// #     Ok(())
+        /// # }
+        /// ```
+        pub fn set_keepalive_params(&self, keepalive: TcpKeepalive) -> io::Result<()> {
+            let socket = self.sys();
+            if let Some(time) = keepalive.time {
+                sys::tcp::set_keepalive_time(socket, time)?;
+            }
+            if let Some(interval) = keepalive.interval {
+                sys::tcp::set_keepalive_interval(socket, interval)?;
+            }
+            if let Some(retries) = keepalive.retries {
+                sys::tcp::set_keepalive_retries(socket, retries)?;
+            }
+            sys::tcp::set_keepalive(socket, true)
+        }
+
+        /// Returns the amount of time after which TCP keepalive probes will be
+        /// sent on idle connections.
+        ///
+        /// See [`TcpKeepalive::with_time`].
+        pub fn keepalive_time(&self) -> io::Result<Duration> {
+            sys::tcp::get_keepalive_time(self.sys())
+        }
+
+        /// Returns the time interval between TCP keepalive probes.
+        ///
+        /// See [`TcpKeepalive::with_interval`].
+        pub fn keepalive_interval(&self) -> io::Result<Duration> {
+            sys::tcp::get_keepalive_interval(self.sys())
+        }
+
+        /// Returns the number of TCP keepalive probes that will be sent before
+        /// the connection is dropped.
+        ///
+        /// See [`TcpKeepalive::with_retries`].
+        pub fn keepalive_retries(&self) -> io::Result<u32> {
+            sys::tcp::get_keepalive_retries(self.sys())
+        }
+    }
+
+    /// Sets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// If `linger` is `Some`, closing the socket blocks until all pending data
+    /// is sent or the timeout expires, a zero timeout causes the connection to
+    /// be reset on close. If `None`, closing the socket returns immediately
+    /// and the OS sends any pending data in the background.
+    ///
+    /// The timeout is rounded down to whole seconds.
+    #[cfg(not(target_os = "wasi"))]
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        sys::tcp::set_linger(self.sys(), linger)
+    }
+
+    /// Get the value of the `SO_LINGER` option on this socket.
+    #[cfg(not(target_os = "wasi"))]
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        sys::tcp::get_linger(self.sys())
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// The OS may adjust the value, use [`send_buffer_size`] to get the size
+    /// actually used.
+    ///
+    /// [`send_buffer_size`]: TcpStream::send_buffer_size
+    #[cfg(not(target_os = "wasi"))]
+    pub fn set_send_buffer_size(&self, size: u32) -> io::Result<()> {
+        sys::tcp::set_send_buffer_size(self.sys(), size)
+    }
+
+    /// Get the value of the `SO_SNDBUF` option on this socket.
+    #[cfg(not(target_os = "wasi"))]
+    pub fn send_buffer_size(&self) -> io::Result<u32> {
+        sys::tcp::get_send_buffer_size(self.sys())
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// The OS may adjust the value, use [`recv_buffer_size`] to get the size
+    /// actually used. Note that the TCP window scale is determined when the
+    /// connection is established, use [`TcpSocket::set_recv_buffer_size`] to
+    /// set the size before connecting.
+    ///
+    /// [`recv_buffer_size`]: TcpStream::recv_buffer_size
+    #[cfg(not(target_os = "wasi"))]
+    pub fn set_recv_buffer_size(&self, size: u32) -> io::Result<()> {
+        sys::tcp::set_recv_buffer_size(self.sys(), size)
+    }
+
+    /// Get the value of the `SO_RCVBUF` option on this socket.
+    #[cfg(not(target_os = "wasi"))]
+    pub fn recv_buffer_size(&self) -> io::Result<u32> {
+        sys::tcp::get_recv_buffer_size(self.sys())
+    }
+
+    /// Sets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// This is the maximum amount of time transmitted data may remain
+    /// unacknowledged before the connection is forcibly closed, with an error
+    /// of `ETIMEDOUT`. A zero timeout uses the system default.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_user_timeout(&self, timeout: Duration) -> io::Result<()> {
+        sys::tcp::set_user_timeout(self.sys(), timeout)
+    }
+
+    /// Get the value of the `TCP_USER_TIMEOUT` option on this socket.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn user_timeout(&self) -> io::Result<Duration> {
+        sys::tcp::get_user_timeout(self.sys())
+    }
+
+    /// Sets the value of the `TCP_CORK` option on this socket.
+    ///
+    /// If set, partial frames are not sent until the option is unset again,
+    /// or after 200 milliseconds. This can be used to combine multiple writes
+    /// into full frames.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_cork(&self, cork: bool) -> io::Result<()> {
+        sys::tcp::set_cork(self.sys(), cork)
+    }
+
+    /// Get the value of the `TCP_CORK` option on this socket.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn cork(&self) -> io::Result<bool> {
+        sys::tcp::get_cork(self.sys())
+    }
+
+    /// Sets the value of the `TCP_QUICKACK` option on this socket.
+    ///
+    /// If set, acknowledgements are sent immediately rather than delayed.
+    /// Note that this option is not permanent, the kernel may switch back to
+    /// delayed acknowledgements based on its own heuristics.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_quickack(&self, quickack: bool) -> io::Result<()> {
+        sys::tcp::set_quickack(self.sys(), quickack)
+    }
+
+    /// Get the value of the `TCP_QUICKACK` option on this socket.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn quickack(&self) -> io::Result<bool> {
+        sys::tcp::get_quickack(self.sys())
+    }
+
+    /// Sets the value of the `TCP_NOTSENT_LOWAT` option on this socket.
+    ///
+    /// This limits the number of unsent bytes in the send buffer, the socket
+    /// is only writable once the amount of unsent data drops below `lowat`
+    /// bytes.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_notsent_lowat(&self, lowat: u32) -> io::Result<()> {
+        sys::tcp::set_notsent_lowat(self.sys(), lowat)
+    }
+
+    /// Get the value of the `TCP_NOTSENT_LOWAT` option on this socket.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn notsent_lowat(&self) -> io::Result<u32> {
+        sys::tcp::get_notsent_lowat(self.sys())
+    }
+
+    /// Sets the value of the `TCP_CONGESTION` option on this socket, selecting
+    /// the congestion control algorithm, e.g. `"reno"` or `"cubic"`.
+    ///
+    /// Only algorithms listed in
+    /// `/proc/sys/net/ipv4/tcp_allowed_congestion_control` can be selected by
+    /// unprivileged processes.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_congestion(&self, algorithm: &str) -> io::Result<()> {
+        sys::tcp::set_congestion(self.sys(), algorithm)
+    }
+
+    /// Get the value of the `TCP_CONGESTION` option on this socket, i.e. the
+    /// name of the congestion control algorithm.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn congestion(&self) -> io::Result<String> {
+        sys::tcp::get_congestion(self.sys())
+    }
+
+    /// Returns information about the TCP connection, such as the round trip
+    /// time and congestion window, using the `TCP_INFO` option.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::TcpStream;
+    ///
+    /// # let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    /// # let addr = listener.local_addr()?;
+    /// let stream = TcpStream::connect(addr)?;
+    /// # let _ = listener.accept()?;
+    ///
+    /// let info = stream.tcp_info()?;
+    /// println!("rtt: {:?}, cwnd: {}", info.rtt(), info.congestion_window());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn tcp_info(&self) -> io::Result<TcpInfo> {
+        sys::tcp::get_tcp_info(self.sys())
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -208,6 +699,30 @@ impl TcpStream {
         self.inner.peek(buf)
     }
 
+    /// Sends data on the socket, passing `flags` to `send(2)`.
+    ///
+    /// On success, returns the number of bytes written.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn send_with_flags(&self, buf: &[u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::tcp::send_with_flags(inner.as_raw_fd(), buf, flags.bits()))
+    }
+
+    /// Receives data from the socket, passing `flags` to `recv(2)`.
+    ///
+    /// On success, returns the number of bytes read.
+    ///
+    /// With [`MsgFlags::TRUNC`] the returned length can be larger than
+    /// `buf.len()`. Don't use it to slice `buf` without comparing it to
+    /// `buf.len()` first.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn recv_with_flags(&self, buf: &mut [u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::tcp::recv_with_flags(inner.as_raw_fd(), buf, flags.bits()))
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
@@ -265,6 +780,16 @@ impl TcpStream {
     {
         self.inner.do_io(|_| f())
     }
+
+    /// Returns the socket for use with the `sys::tcp` functions.
+    #[cfg(not(target_os = "wasi"))]
+    pub(crate) fn sys(&self) -> sys::tcp::TcpSocket {
+        #[cfg(unix)]
+        let socket = self.inner.as_raw_fd();
+        #[cfg(windows)]
+        let socket = self.inner.as_raw_socket() as _;
+        socket
+    }
 }
 
 impl Read for TcpStream {
@@ -293,6 +818,12 @@ impl Write for TcpStream {
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        // Unlike `write`, std's `write_vectored` can raise `SIGPIPE`.
+        #[cfg(unix)]
+        return self
+            .inner
+            .do_io(|inner| sys::tcp::write_vectored(inner.as_raw_fd(), bufs));
+        #[cfg(not(unix))]
         self.inner.do_io(|inner| (&*inner).write_vectored(bufs))
     }
 
@@ -307,6 +838,12 @@ impl<'a> Write for &'a TcpStream {
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        // Unlike `write`, std's `write_vectored` can raise `SIGPIPE`.
+        #[cfg(unix)]
+        return self
+            .inner
+            .do_io(|inner| sys::tcp::write_vectored(inner.as_raw_fd(), bufs));
+        #[cfg(not(unix))]
         self.inner.do_io(|inner| (&*inner).write_vectored(bufs))
     }
 
@@ -337,6 +874,14 @@ impl event::Source for TcpStream {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.pause(registry)
+    }
+
+    fn resume(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.resume(registry)
+    }
 }
 
 impl fmt::Debug for TcpStream {
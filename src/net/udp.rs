@@ -8,10 +8,18 @@
 //! [portability guidelines]: ../struct.Poll.html#portability
 
 use crate::io_source::IoSource;
+#[cfg(unix)]
+use crate::net::MsgFlags;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::RecvMeta;
+#[cfg(target_os = "linux")]
+use crate::net::ZeroCopyCompletion;
 use crate::{event, sys, Interest, Registry, Token};
 
 use std::fmt;
 use std::io;
+#[cfg(unix)]
+use std::io::{IoSlice, IoSliceMut};
 use std::net;
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 #[cfg(unix)]
@@ -317,6 +325,230 @@ impl UdpSocket {
         self.inner.do_io(|inner| inner.peek(buf))
     }
 
+    /// Sends data on the socket to the socket's peer, passing `flags` to
+    /// `send(2)`.
+    ///
+    /// On success, returns the number of bytes written.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn send_with_flags(&self, buf: &[u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_with_flags(inner.as_raw_fd(), buf, flags.bits()))
+    }
+
+    /// Receives data from the socket's peer, passing `flags` to `recv(2)`.
+    ///
+    /// On success, returns the number of bytes read.
+    ///
+    /// With [`MsgFlags::TRUNC`] the returned length is the full length of the
+    /// datagram, which can be larger than `buf.len()`. Don't use it to slice
+    /// `buf` without comparing it to `buf.len()` first.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn recv_with_flags(&self, buf: &mut [u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_with_flags(inner.as_raw_fd(), buf, flags.bits()))
+    }
+
+    /// Sends data on the socket to the given address, passing `flags` to
+    /// `sendto(2)`.
+    ///
+    /// On success, returns the number of bytes written.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn send_to_with_flags(
+        &self,
+        buf: &[u8],
+        target: SocketAddr,
+        flags: MsgFlags,
+    ) -> io::Result<usize> {
+        self.inner.do_io(|inner| {
+            sys::udp::send_to_with_flags(inner.as_raw_fd(), buf, target, flags.bits())
+        })
+    }
+
+    /// Receives data from the socket, passing `flags` to `recvfrom(2)`.
+    ///
+    /// On success, returns the number of bytes read and the address from
+    /// whence the data came.
+    ///
+    /// With [`MsgFlags::TRUNC`] the returned length is the full length of the
+    /// datagram, which can be larger than `buf.len()`. Don't use it to slice
+    /// `buf` without comparing it to `buf.len()` first.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn recv_from_with_flags(
+        &self,
+        buf: &mut [u8],
+        flags: MsgFlags,
+    ) -> io::Result<(usize, SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_from_with_flags(inner.as_raw_fd(), buf, flags.bits()))
+    }
+
+    /// Sends data from multiple buffers as a single datagram to the socket's
+    /// peer, using `sendmsg(2)`.
+    ///
+    /// On success, returns the number of bytes written.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn send_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_vectored(inner.as_raw_fd(), bufs))
+    }
+
+    /// Receives a single datagram from the socket's peer into multiple
+    /// buffers, using `recvmsg(2)`.
+    ///
+    /// On success, returns the number of bytes read.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn recv_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_vectored(inner.as_raw_fd(), bufs))
+    }
+
+    /// Sends data from multiple buffers as a single datagram to the given
+    /// address, using `sendmsg(2)`.
+    ///
+    /// On success, returns the number of bytes written.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn send_to_vectored(&self, bufs: &[IoSlice<'_>], target: SocketAddr) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_to_vectored(inner.as_raw_fd(), bufs, target))
+    }
+
+    /// Receives a single datagram into multiple buffers, using `recvmsg(2)`.
+    ///
+    /// On success, returns the number of bytes read and the address from
+    /// whence the data came.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn recv_from_vectored(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_from_vectored(inner.as_raw_fd(), bufs))
+    }
+
+    /// Receives multiple datagrams with a single system call, using
+    /// `recvmmsg(2)`.
+    ///
+    /// Each datagram is received into the next buffer in `bufs`, its length
+    /// and sender are stored in the matching entry in `meta`. Receives no
+    /// more datagrams than the length of `bufs` or `meta`, the OS may limit
+    /// the number further (Linux receives at most 1024 per call).
+    ///
+    /// On success, returns the number of datagrams received. Returns a
+    /// [`WouldBlock`] error if no datagrams are pending. Datagrams that don't
+    /// fit in their buffer are truncated, see [`RecvMeta::is_truncated`].
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::io::{self, IoSliceMut};
+    ///
+    /// use mio::net::{RecvMeta, UdpSocket};
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// let mut storage = vec![[0; 1500]; 8];
+    /// let mut bufs: Vec<_> = storage.iter_mut().map(|buf| IoSliceMut::new(buf)).collect();
+    /// let mut meta = [RecvMeta::default(); 8];
+    /// match socket.recv_many(&mut bufs, &mut meta) {
+    ///     Ok(n) => {
+    ///         for (buf, meta) in bufs.iter().zip(meta.iter()).take(n) {
+    ///             println!("received {:?} from {}", &buf[..meta.len()], meta.addr());
+    ///         }
+    ///     }
+    ///     Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {}
+    ///     Err(err) => return Err(err.into()),
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn recv_many(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        meta: &mut [RecvMeta],
+    ) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_many(inner, bufs, meta))
+    }
+
+    /// Sends multiple datagrams with a single system call, using
+    /// `sendmmsg(2)`.
+    ///
+    /// Each buffer in `bufs` is sent as a datagram to the matching address in
+    /// `addrs`, or to the connected peer if `addrs` is empty. The OS may limit
+    /// the number of datagrams sent by a single call (Linux sends at most
+    /// 1024).
+    ///
+    /// On success, returns the number of datagrams sent, which may be less
+    /// than the number of buffers. Returns an error if `addrs` is not empty
+    /// and its length differs from `bufs`.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn send_many(&self, bufs: &[IoSlice<'_>], addrs: &[SocketAddr]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_many(inner, bufs, addrs))
+    }
+
+    /// Sends `buf` to the connected peer as multiple datagrams of
+    /// `segment_size` bytes, using UDP generic segmentation offload
+    /// (`UDP_SEGMENT`). The last datagram may be smaller.
+    ///
+    /// The kernel, or the network card, splits the buffer into datagrams,
+    /// which is cheaper than sending each datagram separately. The number of
+    /// segments is limited by the kernel, to 64 on most versions.
+    ///
+    /// On success, returns the number of bytes sent.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn send_segmented(&self, buf: &[u8], segment_size: u16) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_segmented(inner, buf, None, segment_size))
+    }
+
+    /// Sends `buf` to `target` as multiple datagrams of `segment_size`
+    /// bytes, see [`send_segmented`].
+    ///
+    /// [`send_segmented`]: UdpSocket::send_segmented
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn send_to_segmented(
+        &self,
+        buf: &[u8],
+        target: SocketAddr,
+        segment_size: u16,
+    ) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_segmented(inner, buf, Some(target), segment_size))
+    }
+
+    /// Receives a single datagram, or multiple coalesced datagrams if
+    /// [`set_gro`] is enabled, returning its length, sender and segment size.
+    ///
+    /// If the datagram doesn't fit in `buf` the excess bytes are discarded,
+    /// see [`RecvMeta::is_truncated`].
+    ///
+    /// [`set_gro`]: UdpSocket::set_gro
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn recv_with_meta(&self, buf: &mut [u8]) -> io::Result<RecvMeta> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_with_meta(inner, buf))
+    }
+
     /// Connects the UDP socket setting the default destination for `send()`
     /// and limiting packets that are read via `recv` from the address specified
     /// in `addr`.
@@ -544,6 +776,106 @@ impl UdpSocket {
         sys::udp::only_v6(&self.inner)
     }
 
+    /// Sets the value of the `UDP_GRO` option on this socket.
+    ///
+    /// When enabled the kernel may coalesce multiple datagrams from the same
+    /// sender into a single buffer, reducing the number of receive calls. The
+    /// size of the individual datagrams is returned by
+    /// [`RecvMeta::segment_size`] when receiving using [`recv_with_meta`] or
+    /// [`recv_many`]. Buffers should be large enough for the coalesced
+    /// datagrams, up to 64 KiB, otherwise data is truncated.
+    ///
+    /// [`recv_with_meta`]: UdpSocket::recv_with_meta
+    /// [`recv_many`]: UdpSocket::recv_many
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn set_gro(&self, gro: bool) -> io::Result<()> {
+        sys::udp::set_gro(self.inner.as_raw_fd(), gro)
+    }
+
+    /// Get the value of the `UDP_GRO` option on this socket.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn gro(&self) -> io::Result<bool> {
+        sys::udp::get_gro(self.inner.as_raw_fd())
+    }
+
+    /// Sets the value of the `SO_ZEROCOPY` option on this socket.
+    ///
+    /// This must be enabled before using [`send_zerocopy`].
+    ///
+    /// [`send_zerocopy`]: UdpSocket::send_zerocopy
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_zerocopy(&self, zerocopy: bool) -> io::Result<()> {
+        sys::udp::set_zerocopy(self.inner.as_raw_fd(), zerocopy)
+    }
+
+    /// Get the value of the `SO_ZEROCOPY` option on this socket.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn zerocopy(&self) -> io::Result<bool> {
+        sys::udp::get_zerocopy(self.inner.as_raw_fd())
+    }
+
+    /// Send data using `MSG_ZEROCOPY`, avoiding copying `buf` into the kernel.
+    ///
+    /// Returns the number of bytes sent, like [`UdpSocket::send`].
+    ///
+    /// Unlike a regular send the kernel keeps using `buf` after this returns,
+    /// so `buf` must not be modified or dropped until the kernel signals the
+    /// completion of the send. Completions are signaled with an error event
+    /// ([`Event::is_error`]) for the registration of this socket, after which
+    /// they can be read using [`recv_zerocopy_completion`]. The error event is
+    /// generated regardless of the interests the socket is registered with.
+    ///
+    /// [`set_zerocopy`] must be enabled first, otherwise this returns an
+    /// error.
+    ///
+    /// # Notes
+    ///
+    /// **A completion looks like an error on the socket.** On Linux the
+    /// completion event is `EPOLLERR`, if the socket is also writable the
+    /// event returns true for [`Event::is_write_closed`] as well, even though
+    /// the socket is still open. An event loop using zerocopy sends must not
+    /// close the socket on such an event. Instead it should read all
+    /// completions using [`recv_zerocopy_completion`], until it returns a
+    /// [`WouldBlock`] error, and then use [`take_error`] to check for an
+    /// actual error.
+    ///
+    /// [`Event::is_error`]: crate::event::Event::is_error
+    /// [`Event::is_write_closed`]: crate::event::Event::is_write_closed
+    /// [`recv_zerocopy_completion`]: UdpSocket::recv_zerocopy_completion
+    /// [`set_zerocopy`]: UdpSocket::set_zerocopy
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [`take_error`]: UdpSocket::take_error
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn send_zerocopy(&self, buf: &[u8]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_zerocopy(inner.as_raw_fd(), buf))
+    }
+
+    /// Receive the next zerocopy completion from the socket's error queue.
+    ///
+    /// Returns a [`WouldBlock`] error if there are no pending completions. See
+    /// [`send_zerocopy`].
+    ///
+    /// If the next message on the error queue is not a zerocopy completion,
+    /// e.g. an ICMP error when `IP_RECVERR` is enabled, the message is removed
+    /// from the queue and the error it carries is returned. Messages that
+    /// can't be parsed are returned as an [`InvalidData`] error.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [`InvalidData`]: io::ErrorKind::InvalidData
+    /// [`send_zerocopy`]: UdpSocket::send_zerocopy
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn recv_zerocopy_completion(&self) -> io::Result<ZeroCopyCompletion> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_zerocopy_completion(inner.as_raw_fd()))
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -634,6 +966,14 @@ impl event::Source for UdpSocket {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.pause(registry)
+    }
+
+    fn resume(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.resume(registry)
+    }
 }
 
 impl fmt::Debug for UdpSocket {
@@ -1,6 +1,8 @@
 use crate::io_source::IoSource;
+use crate::net::MsgFlags;
 use crate::{event, sys, Interest, Registry, Token};
 
+use std::io::{IoSlice, IoSliceMut};
 use std::net::Shutdown;
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use std::os::unix::net;
@@ -98,6 +100,102 @@ impl UnixDatagram {
         self.inner.do_io(|inner| inner.send(buf))
     }
 
+    /// Sends data on the socket to the socket's peer, passing `flags` to
+    /// `send(2)`.
+    ///
+    /// On success, returns the number of bytes written.
+    pub fn send_with_flags(&self, buf: &[u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::send_with_flags(inner.as_raw_fd(), buf, flags.bits()))
+    }
+
+    /// Receives data from the socket's peer, passing `flags` to `recv(2)`.
+    ///
+    /// On success, returns the number of bytes read.
+    ///
+    /// With [`MsgFlags::TRUNC`] the returned length is the full length of the
+    /// datagram, which can be larger than `buf.len()`. Don't use it to slice
+    /// `buf` without comparing it to `buf.len()` first.
+    pub fn recv_with_flags(&self, buf: &mut [u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::recv_with_flags(inner.as_raw_fd(), buf, flags.bits()))
+    }
+
+    /// Sends data on the socket to the given address, passing `flags` to
+    /// `sendto(2)`.
+    ///
+    /// On success, returns the number of bytes written.
+    pub fn send_to_with_flags<P: AsRef<Path>>(
+        &self,
+        buf: &[u8],
+        path: P,
+        flags: MsgFlags,
+    ) -> io::Result<usize> {
+        self.inner.do_io(|inner| {
+            sys::uds::datagram::send_to_with_flags(inner, buf, path.as_ref(), flags.bits())
+        })
+    }
+
+    /// Receives data from the socket, passing `flags` to `recvfrom(2)`.
+    ///
+    /// On success, returns the number of bytes read and the address from
+    /// whence the data came.
+    ///
+    /// With [`MsgFlags::TRUNC`] the returned length is the full length of the
+    /// datagram, which can be larger than `buf.len()`. Don't use it to slice
+    /// `buf` without comparing it to `buf.len()` first.
+    pub fn recv_from_with_flags(
+        &self,
+        buf: &mut [u8],
+        flags: MsgFlags,
+    ) -> io::Result<(usize, sys::SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::recv_from_with_flags(inner, buf, flags.bits()))
+    }
+
+    /// Sends data from multiple buffers as a single datagram to the socket's
+    /// peer, using `sendmsg(2)`.
+    ///
+    /// On success, returns the number of bytes written.
+    pub fn send_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::send_vectored(inner.as_raw_fd(), bufs))
+    }
+
+    /// Receives a single datagram from the socket's peer into multiple
+    /// buffers, using `recvmsg(2)`.
+    ///
+    /// On success, returns the number of bytes read.
+    pub fn recv_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::recv_vectored(inner.as_raw_fd(), bufs))
+    }
+
+    /// Sends data from multiple buffers as a single datagram to the given
+    /// address, using `sendmsg(2)`.
+    ///
+    /// On success, returns the number of bytes written.
+    pub fn send_to_vectored<P: AsRef<Path>>(
+        &self,
+        bufs: &[IoSlice<'_>],
+        path: P,
+    ) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::send_to_vectored(inner, bufs, path.as_ref()))
+    }
+
+    /// Receives a single datagram into multiple buffers, using `recvmsg(2)`.
+    ///
+    /// On success, returns the number of bytes read and the address from
+    /// whence the data came.
+    pub fn recv_from_vectored(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, sys::SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::recv_from_vectored(inner, bufs))
+    }
+
     /// Returns the value of the `SO_ERROR` option.
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
@@ -203,6 +301,14 @@ impl event::Source for UnixDatagram {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.pause(registry)
+    }
+
+    fn resume(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.resume(registry)
+    }
 }
 
 impl fmt::Debug for UnixDatagram {
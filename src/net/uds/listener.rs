@@ -1,4 +1,5 @@
 use crate::io_source::IoSource;
+use crate::net::accept::{accept_many, accept_or_shed, FdReserve};
 use crate::net::{SocketAddr, UnixStream};
 use crate::{event, sys, Interest, Registry, Token};
 
@@ -38,6 +39,57 @@ impl UnixListener {
         sys::uds::listener::accept(&self.inner)
     }
 
+    /// Accepts up to `max` new connections, adding them to `accepted`.
+    ///
+    /// This calls [`accept`] until it returns a [`WouldBlock`] error, or `max`
+    /// connections are accepted. Returns the number of connections accepted.
+    /// If this is `max` there may be more connections pending, call
+    /// `accept_many` again before waiting for the next event.
+    ///
+    /// Connections that were aborted by the peer before they were accepted
+    /// (`ECONNABORTED`) are skipped. If no connections are pending this returns
+    /// a [`WouldBlock`] error. If another error occurs after accepting one or
+    /// more connections the accepted connections are returned and the error is
+    /// dropped. Errors that persist, such as running out of file descriptors,
+    /// are returned by the next call.
+    ///
+    /// [`accept`]: UnixListener::accept
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn accept_many(
+        &self,
+        accepted: &mut Vec<(UnixStream, SocketAddr)>,
+        max: usize,
+    ) -> io::Result<usize> {
+        accept_many(|| self.accept(), accepted, max)
+    }
+
+    /// Accepts a new connection, shedding the pending connection if the
+    /// process ran out of file descriptors.
+    ///
+    /// This works like [`accept`], but if accepting fails because no more
+    /// file descriptors are available it uses the file descriptor reserved in
+    /// `reserve` to accept the connection and close it right away. In that
+    /// case `Ok(None)` is returned, after which the listener can continue
+    /// accepting connections. If no connection is pending a [`WouldBlock`]
+    /// error is returned, as with `accept`.
+    ///
+    /// If the connection can't be shed, e.g. because `reserve` doesn't hold
+    /// a file descriptor, the original error is returned, which can be
+    /// checked using [`FdReserve::is_exhausted`]. Any error hit while shedding
+    /// the connection is returned as well.
+    ///
+    /// See [`FdReserve`] for an example.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    ///
+    /// [`accept`]: UnixListener::accept
+    pub fn accept_or_shed(
+        &self,
+        reserve: &mut FdReserve,
+    ) -> io::Result<Option<(UnixStream, SocketAddr)>> {
+        accept_or_shed(|| self.accept(), reserve, |_, _| Ok(()))
+    }
+
     /// Returns the local socket address of this listener.
     pub fn local_addr(&self) -> io::Result<sys::SocketAddr> {
         sys::uds::listener::local_addr(&self.inner)
@@ -71,6 +123,14 @@ impl event::Source for UnixListener {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.pause(registry)
+    }
+
+    fn resume(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.resume(registry)
+    }
 }
 
 impl fmt::Debug for UnixListener {
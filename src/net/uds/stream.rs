@@ -1,4 +1,8 @@
 use crate::io_source::IoSource;
+use crate::net::connect_status::finish_connect;
+use crate::net::{ConnectStatus, MsgFlags};
+#[cfg(all(feature = "os-ext", any(target_os = "android", target_os = "linux")))]
+use crate::unix::pipe;
 use crate::{event, sys, Interest, Registry, Token};
 
 use std::fmt;
@@ -8,6 +12,10 @@ use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use std::os::unix::net;
 use std::path::Path;
 
+cfg_send_file! {
+    use std::fs::File;
+}
+
 /// A non-blocking Unix stream socket.
 pub struct UnixStream {
     inner: IoSource<net::UnixStream>,
@@ -18,6 +26,10 @@ impl UnixStream {
     ///
     /// This may return a `WouldBlock` in which case the socket connection
     /// cannot be completed immediately. Usually it means the backlog is full.
+    ///
+    /// The returned stream may not be connected yet, see
+    /// [`UnixStream::finish_connect`] to check whether the connection is
+    /// established.
     pub fn connect<P: AsRef<Path>>(path: P) -> io::Result<UnixStream> {
         sys::uds::stream::connect(path.as_ref()).map(UnixStream::from_std)
     }
@@ -64,6 +76,99 @@ impl UnixStream {
         self.inner.take_error()
     }
 
+    /// Check whether a non-blocking connect, started by
+    /// [`UnixStream::connect`], has completed.
+    ///
+    /// Call this after receiving a (writable) event. Returns
+    /// [`ConnectStatus::Connected`] once the connection is established,
+    /// [`ConnectStatus::InProgress`] if it isn't established yet, or the error
+    /// of the connect if it failed. See [`TcpStream::finish_connect`].
+    ///
+    /// [`TcpStream::finish_connect`]: crate::net::TcpStream::finish_connect
+    pub fn finish_connect(&self) -> io::Result<ConnectStatus> {
+        finish_connect(self.take_error(), || self.peer_addr())
+    }
+
+    /// Sends data on the socket, passing `flags` to `send(2)`.
+    ///
+    /// On success, returns the number of bytes written.
+    pub fn send_with_flags(&self, buf: &[u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::stream::send_with_flags(inner.as_raw_fd(), buf, flags.bits()))
+    }
+
+    /// Receives data from the socket, passing `flags` to `recv(2)`.
+    ///
+    /// On success, returns the number of bytes read.
+    ///
+    /// With [`MsgFlags::TRUNC`] the returned length can be larger than
+    /// `buf.len()`. Don't use it to slice `buf` without comparing it to
+    /// `buf.len()` first.
+    pub fn recv_with_flags(&self, buf: &mut [u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::stream::recv_with_flags(inner.as_raw_fd(), buf, flags.bits()))
+    }
+
+    cfg_send_file! {
+        /// Send up to `len` bytes of `file`, starting at `offset`, to the stream
+        /// without copying the data through user space, using `sendfile(2)`.
+        ///
+        /// Returns the number of bytes sent, which may be less than `len`, or zero
+        /// if `offset` is at or past the end of the file. The file's own offset is
+        /// not changed.
+        ///
+        /// If the stream's send buffer is full this returns a [`WouldBlock`]
+        /// error, wait for a writable event before trying again. As with
+        /// [`write`], call `send_file` until it returns `WouldBlock` (or all
+        /// data is sent) before waiting for the next event.
+        ///
+        /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+        /// [`write`]: Write::write
+        pub fn send_file(&self, file: &File, offset: u64, len: usize) -> io::Result<usize> {
+            self.inner
+                .do_io(|inner| sys::uds::stream::send_file(inner.as_raw_fd(), file, offset, len))
+        }
+    }
+
+    /// Move up to `len` bytes from the stream into `pipe` without copying
+    /// the data through user space, using `splice(2)`.
+    ///
+    /// Returns the number of bytes moved, zero if the stream was shut down
+    /// for reading. Returns a [`WouldBlock`] error if either no data is
+    /// available on the stream or `pipe` is full, wait for a readable event on
+    /// the stream or a writable event on `pipe` respectively.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    #[cfg(all(feature = "os-ext", any(target_os = "android", target_os = "linux")))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(feature = "os-ext", any(target_os = "android", target_os = "linux"))))
+    )]
+    pub fn splice_to(&self, pipe: &pipe::Sender, len: usize) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::stream::splice(inner.as_raw_fd(), pipe.as_raw_fd(), len))
+    }
+
+    /// Move up to `len` bytes from `pipe` into the stream without copying the
+    /// data through user space, using `splice(2)`.
+    ///
+    /// Returns the number of bytes moved, zero if the sending end of `pipe`
+    /// was closed and no data is left. Returns a [`WouldBlock`] error if either
+    /// `pipe` is empty or the stream's send buffer is full, wait for a
+    /// readable event on `pipe` or a writable event on the stream
+    /// respectively.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    #[cfg(all(feature = "os-ext", any(target_os = "android", target_os = "linux")))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(feature = "os-ext", any(target_os = "android", target_os = "linux"))))
+    )]
+    pub fn splice_from(&self, pipe: &pipe::Receiver, len: usize) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::stream::splice(pipe.as_raw_fd(), inner.as_raw_fd(), len))
+    }
+
     /// Shuts down the read, write, or both halves of this connection.
     ///
     /// This function will cause all pending and future I/O calls on the
@@ -164,11 +269,13 @@ impl<'a> Read for &'a UnixStream {
 
 impl Write for UnixStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.inner.do_io(|inner| (&*inner).write(buf))
+        self.inner
+            .do_io(|inner| sys::uds::stream::send_with_flags(inner.as_raw_fd(), buf, 0))
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|inner| (&*inner).write_vectored(bufs))
+        self.inner
+            .do_io(|inner| sys::uds::stream::write_vectored(inner.as_raw_fd(), bufs))
     }
 
     fn flush(&mut self) -> io::Result<()> {
@@ -178,11 +285,13 @@ impl Write for UnixStream {
 
 impl<'a> Write for &'a UnixStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.inner.do_io(|inner| (&*inner).write(buf))
+        self.inner
+            .do_io(|inner| sys::uds::stream::send_with_flags(inner.as_raw_fd(), buf, 0))
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|inner| (&*inner).write_vectored(bufs))
+        self.inner
+            .do_io(|inner| sys::uds::stream::write_vectored(inner.as_raw_fd(), bufs))
     }
 
     fn flush(&mut self) -> io::Result<()> {
@@ -212,6 +321,14 @@ impl event::Source for UnixStream {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.pause(registry)
+    }
+
+    fn resume(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.resume(registry)
+    }
 }
 
 impl fmt::Debug for UnixStream {
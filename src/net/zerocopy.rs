@@ -0,0 +1,55 @@
+/// Completion of one or more zerocopy sends, as returned by
+/// [`TcpStream::recv_zerocopy_completion`] and
+/// [`UdpSocket::recv_zerocopy_completion`].
+///
+/// Each call to `send_zerocopy` that sends data is numbered, starting at zero
+/// for each socket. A completion covers a range of those calls, the kernel
+/// merges consecutive completions. Once a call is completed its buffer may be
+/// reused or dropped.
+///
+/// [`TcpStream::recv_zerocopy_completion`]: crate::net::TcpStream::recv_zerocopy_completion
+/// [`UdpSocket::recv_zerocopy_completion`]: crate::net::UdpSocket::recv_zerocopy_completion
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ZeroCopyCompletion {
+    pub(crate) start: u32,
+    pub(crate) end: u32,
+    pub(crate) copied: bool,
+}
+
+impl ZeroCopyCompletion {
+    /// Returns the number of the first `send_zerocopy` call that completed.
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// Returns the number of the last `send_zerocopy` call that completed,
+    /// inclusive.
+    ///
+    /// The counter wraps around after `u32::MAX` calls, in which case `end`
+    /// is smaller than [`start`]. Use [`iter`] to handle this.
+    ///
+    /// [`start`]: ZeroCopyCompletion::start
+    /// [`iter`]: ZeroCopyCompletion::iter
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+
+    /// Returns an iterator over the numbers of all `send_zerocopy` calls that
+    /// completed, from [`start`] up to and including [`end`], handling the
+    /// counter wrapping around.
+    ///
+    /// [`start`]: ZeroCopyCompletion::start
+    /// [`end`]: ZeroCopyCompletion::end
+    pub fn iter(&self) -> impl Iterator<Item = u32> {
+        let start = self.start;
+        (0..=self.end.wrapping_sub(start)).map(move |n| start.wrapping_add(n))
+    }
+
+    /// Returns true if the kernel copied the data instead of sending it from
+    /// the buffers directly, e.g. when sending over the loopback device.
+    ///
+    /// If this happens often zerocopy sends are not beneficial for the socket.
+    pub fn copied(&self) -> bool {
+        self.copied
+    }
+}
@@ -1,8 +1,15 @@
+use crate::event::Readiness;
+#[cfg(feature = "record")]
+use crate::record::{Recorder, Replay};
 use crate::{event, sys, Events, Interest, Token};
 use log::trace;
+#[cfg(feature = "record")]
+use std::io::Write;
 #[cfg(unix)]
 use std::os::unix::io::{AsRawFd, RawFd};
-use std::time::Duration;
+#[cfg(feature = "record")]
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
 use std::{fmt, io};
 
 /// Polls for readiness events on all registered values.
@@ -226,11 +233,20 @@ use std::{fmt, io};
 /// [`Poll::poll`]: struct.Poll.html#method.poll
 pub struct Poll {
     registry: Registry,
+    /// Whether or not to retry `poll` calls that were interrupted, see
+    /// [`Poll::set_retry_interrupted`].
+    retry_interrupted: bool,
+    /// Log to replay events from, see [`Poll::replay`]. This is a `Mutex` to
+    /// keep `Poll` `Sync`, it's never locked.
+    #[cfg(feature = "record")]
+    replay: Option<Mutex<Replay>>,
 }
 
 /// Registers I/O resources.
 pub struct Registry {
     selector: sys::Selector,
+    #[cfg(feature = "record")]
+    recorder: Recorder,
 }
 
 impl Poll {
@@ -270,17 +286,265 @@ impl Poll {
         /// ```
         pub fn new() -> io::Result<Poll> {
             sys::Selector::new().map(|selector| Poll {
-                registry: Registry { selector },
+                registry: Registry {
+                    selector,
+                    #[cfg(feature = "record")]
+                    recorder: Recorder::default(),
+                },
+                retry_interrupted: false,
+                #[cfg(feature = "record")]
+                replay: None,
             })
         }
     }
 
+    cfg_record! {
+        /// Create a `Poll` instance that replays the events recorded in `log`.
+        ///
+        /// The `log` must be written by a `Poll` instance, see
+        /// [`start_recording`]. Each call to [`poll`] returns the events of
+        /// the next recorded call, without blocking and without consulting
+        /// the system selector, ignoring the timeout. If the recorded call
+        /// returned more events than fit in the supplied [`Events`] the
+        /// remaining events are returned by the next call. Once all recorded
+        /// calls are replayed [`poll`] returns an error of kind
+        /// [`UnexpectedEof`].
+        ///
+        /// Event sources can be registered as normal, but only the events in
+        /// the `log` are returned. This includes events posted using
+        /// [`Registry::post`], those are returned as they were recorded.
+        ///
+        /// [`start_recording`]: Poll::start_recording
+        /// [`poll`]: Poll::poll
+        /// [`Events`]: crate::Events
+        /// [`UnexpectedEof`]: io::ErrorKind::UnexpectedEof
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use std::error::Error;
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// use mio::event::Readiness;
+        /// use mio::{Events, Poll, Token};
+        /// use std::io::Cursor;
+        /// use std::sync::{Arc, Mutex};
+        /// use std::time::Duration;
+        ///
+        /// # #[derive(Clone, Default)]
+        /// # struct SharedLog(Arc<Mutex<Vec<u8>>>);
+        /// # impl std::io::Write for SharedLog {
+        /// #     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        /// #         self.0.lock().unwrap().write(buf)
+        /// #     }
+        /// #     fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+        /// # }
+        /// // `SharedLog` is a `Vec<u8>` that can be shared between threads.
+        /// let log = SharedLog::default();
+        /// let mut events = Events::with_capacity(16);
+        ///
+        /// let mut poll = Poll::new()?;
+        /// poll.start_recording(log.clone())?;
+        /// poll.registry().post(Token(1), Readiness::WRITABLE)?;
+        /// poll.poll(&mut events, Some(Duration::from_millis(10)))?;
+        /// poll.stop_recording()?;
+        ///
+        /// let log = log.0.lock().unwrap().clone();
+        /// let mut replay = Poll::replay(Cursor::new(log))?;
+        /// replay.poll(&mut events, None)?;
+        /// let event = events.iter().next().unwrap();
+        /// assert_eq!(event.token(), Token(1));
+        /// assert!(event.is_writable());
+        ///
+        /// // The log only contained a single call.
+        /// assert!(replay.poll(&mut events, None).is_err());
+        /// #     Ok(())
+        /// # }
+        /// ```
+        pub fn replay<R>(log: R) -> io::Result<Poll>
+        where
+            R: io::Read + Send + 'static,
+        {
+            let reader = crate::record::Reader::new(Box::new(log) as Box<dyn io::Read + Send>)?;
+            let mut poll = Poll::new()?;
+            poll.replay = Some(Mutex::new(Replay::new(reader)));
+            Ok(poll)
+        }
+    }
+
+    cfg_os_poll! {
+        /// Start tracking the registrations made with this `Poll` instance,
+        /// required to use [`after_fork`].
+        ///
+        /// Tracking is disabled by default as it adds some overhead to every
+        /// (re|de)registration. It applies to all clones of the [`Registry`],
+        /// but registrations made before calling this function are not
+        /// tracked, so it should be called before registering any
+        /// [`event::Source`]s.
+        ///
+        /// Tracking registrations is also required to pause and resume
+        /// [`SourceFd`], see [`Registry::pause`].
+        ///
+        /// [`after_fork`]: Poll::after_fork
+        /// [`event::Source`]: ./event/trait.Source.html
+        /// [`SourceFd`]: crate::unix::SourceFd
+        #[cfg(unix)]
+        #[cfg_attr(docsrs, doc(cfg(unix)))]
+        pub fn track_registrations(&self) {
+            self.registry.selector.track_registrations()
+        }
+
+        /// Recreate the system selector after a call to `fork(2)`.
+        ///
+        /// After forking the child process shares the system selector (e.g.
+        /// the epoll instance) with the parent process, meaning that
+        /// registrations and events made in one process affect the other. This
+        /// function should be called in the child process, it creates a new
+        /// system selector and registers all [`event::Source`]s that are
+        /// currently registered again, using the same token and interests.
+        /// The parent process is not affected.
+        ///
+        /// Registrations must be tracked using [`track_registrations`],
+        /// otherwise this returns an error.
+        ///
+        /// [`event::Source`]: ./event/trait.Source.html
+        /// [`track_registrations`]: Poll::track_registrations
+        ///
+        /// # Notes
+        ///
+        /// Mio keeps track of the registrations per file descriptor, event
+        /// sources that are dropped without being [deregistered] are skipped
+        /// if their file descriptor is closed. However if the file descriptor
+        /// is reused for a different file that isn't registered, it will be
+        /// registered with the stale token.
+        ///
+        /// Any `Registry` created using [`Registry::try_clone`] before calling
+        /// this function still refers to the old system selector and should
+        /// be recreated. On platforms where the [`Waker`] is backed by kqueue's
+        /// `EVFILT_USER` (FreeBSD, iOS and macOS) the `Waker` must be recreated
+        /// as well, on other platforms it's registered again like any other
+        /// event source.
+        ///
+        /// [deregistered]: Registry::deregister
+        /// [`Waker`]: crate::Waker
+        #[cfg(unix)]
+        #[cfg_attr(docsrs, doc(cfg(unix)))]
+        pub fn after_fork(&mut self) -> io::Result<()> {
+            self.registry.selector.after_fork()
+        }
+    }
+
     /// Create a separate `Registry` which can be used to register
     /// `event::Source`s.
     pub fn registry(&self) -> &Registry {
         &self.registry
     }
 
+    /// Set whether or not [`poll`] should retry the call to the system
+    /// selector if it was interrupted (if `EINTR` was returned).
+    ///
+    /// When enabled an interrupted call is retried with the timeout adjusted
+    /// for the time already spent waiting, so [`poll`] never returns an error
+    /// of kind [`Interrupted`]. This is disabled by default, meaning the error
+    /// is returned to the caller.
+    ///
+    /// [`poll`]: Poll::poll
+    /// [`Interrupted`]: io::ErrorKind::Interrupted
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::Duration;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// poll.set_retry_interrupted(true);
+    /// assert!(poll.retry_interrupted());
+    ///
+    /// let mut events = Events::with_capacity(1024);
+    /// // This will not return an `Interrupted` error if a signal arrives.
+    /// poll.poll(&mut events, Some(Duration::from_millis(10)))?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn set_retry_interrupted(&mut self, retry: bool) {
+        self.retry_interrupted = retry;
+    }
+
+    /// Returns whether or not [`poll`] retries interrupted calls.
+    ///
+    /// For more information about this option, see
+    /// [`set_retry_interrupted`].
+    ///
+    /// [`poll`]: Poll::poll
+    /// [`set_retry_interrupted`]: Poll::set_retry_interrupted
+    pub fn retry_interrupted(&self) -> bool {
+        self.retry_interrupted
+    }
+
+    cfg_record! {
+        /// Start recording the event stream in `log`.
+        ///
+        /// Once started every successful call to [`poll`], along with the
+        /// timeout and the returned events, and every change made to the
+        /// registrations using the [`Registry`], including the `Registry`s
+        /// created using [`Registry::try_clone`], is written to `log`. See the
+        /// [`record`] module for the format. The log can be fed back using
+        /// [`Poll::replay`].
+        ///
+        /// Each record is written using a single call to [`write_all`],
+        /// wrapping `log` in a [`BufWriter`] is recommended. Recording never
+        /// changes the result of the call being recorded: if writing a record
+        /// fails the error is logged and the recording is stopped. If a
+        /// recording is already in progress it's stopped first, see
+        /// [`stop_recording`].
+        ///
+        /// [`poll`]: Poll::poll
+        /// [`record`]: crate::record
+        /// [`write_all`]: Write::write_all
+        /// [`BufWriter`]: std::io::BufWriter
+        /// [`stop_recording`]: Poll::stop_recording
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use std::error::Error;
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// use mio::{Events, Poll};
+        /// use std::fs::File;
+        /// use std::io::BufWriter;
+        /// use std::time::Duration;
+        ///
+        /// # let path = std::env::temp_dir().join("mio-start-recording-doc.log");
+        /// let mut poll = Poll::new()?;
+        /// let mut events = Events::with_capacity(1024);
+        ///
+        /// poll.start_recording(BufWriter::new(File::create(&path)?))?;
+        /// poll.poll(&mut events, Some(Duration::from_millis(10)))?;
+        /// poll.stop_recording()?;
+        /// # std::fs::remove_file(path)?;
+        /// #     Ok(())
+        /// # }
+        /// ```
+        pub fn start_recording<W>(&mut self, log: W) -> io::Result<()>
+        where
+            W: Write + Send + 'static,
+        {
+            self.registry.recorder.start(Box::new(log))
+        }
+
+        /// Stop recording the event stream, flushing and dropping the log
+        /// passed to [`start_recording`]. Does nothing if no recording is in
+        /// progress.
+        ///
+        /// [`start_recording`]: Poll::start_recording
+        pub fn stop_recording(&mut self) -> io::Result<()> {
+            self.registry.recorder.stop()
+        }
+    }
+
     /// Wait for readiness events
     ///
     /// Blocks the current thread and waits for readiness events for any of the
@@ -316,9 +580,12 @@ impl Poll {
     ///
     /// # Notes
     ///
-    /// This returns any errors without attempting to retry, previous versions
-    /// of Mio would automatically retry the poll call if it was interrupted
-    /// (if `EINTR` was returned).
+    /// By default this returns any errors without attempting to retry,
+    /// previous versions of Mio would automatically retry the poll call if it
+    /// was interrupted (if `EINTR` was returned). This behaviour can be
+    /// enabled again using [`set_retry_interrupted`].
+    ///
+    /// [`set_retry_interrupted`]: Poll::set_retry_interrupted
     ///
     /// # Examples
     ///
@@ -375,7 +642,124 @@ impl Poll {
     ///
     /// [struct]: #
     pub fn poll(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
-        self.registry.selector.select(events.sys(), timeout)
+        #[cfg(feature = "record")]
+        if let Some(replay) = self.replay.as_mut() {
+            return replay
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .poll(events);
+        }
+
+        self.select(events, timeout)?;
+        #[cfg(feature = "record")]
+        self.registry.recorder.poll(timeout, events);
+        Ok(())
+    }
+
+    /// Call the system selector, retrying interrupted calls if enabled.
+    fn select(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        if !self.retry_interrupted {
+            return self.registry.selector.select(events.sys(), timeout);
+        }
+
+        // If the deadline can't be represented we use the original timeout for
+        // every retry, which is close enough for such large timeouts.
+        let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+        let mut timeout = timeout;
+        loop {
+            match self.registry.selector.select(events.sys(), timeout) {
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {
+                    if let Some(deadline) = deadline {
+                        timeout = Some(remaining(deadline));
+                    }
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// Wait for readiness events until `deadline`.
+    ///
+    /// This is similar to [`poll`], but instead of a relative timeout it
+    /// accepts an absolute `deadline`. The function will block until either
+    /// at least one readiness event has been received or `deadline` has passed.
+    ///
+    /// Unlike [`poll`] this function retries the call to the system selector
+    /// if it was interrupted (if `EINTR` was returned) or if it returned early
+    /// without any events, recomputing the remaining timeout each time. If
+    /// `deadline` is already in the past the system selector is still called
+    /// once, with a zero timeout.
+    ///
+    /// The supplied `events` will be cleared and newly received readiness
+    /// events will be pushed onto the end, just like [`poll`]. If `events` has
+    /// a capacity of zero an error is returned.
+    ///
+    /// When replaying a recorded log this returns the events of the next
+    /// recorded poll call, even if it had none.
+    ///
+    /// [`poll`]: Poll::poll
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(1024);
+    ///
+    /// let deadline = Instant::now() + Duration::from_millis(50);
+    /// poll.poll_until(&mut events, deadline)?;
+    /// // No `event::Source`s are registered, so we must have hit the deadline.
+    /// assert!(events.is_empty());
+    /// assert!(Instant::now() >= deadline);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn poll_until(&mut self, events: &mut Events, deadline: Instant) -> io::Result<()> {
+        // Without capacity the selector can never return an event, which would
+        // make us retry until `deadline`.
+        if events.capacity() == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "`Events` must have a non-zero capacity",
+            ));
+        }
+
+        // A replay returns the next recorded poll call, the recorded events
+        // are returned as is.
+        #[cfg(feature = "record")]
+        if self.replay.is_some() {
+            return self.poll(events, Some(remaining(deadline)));
+        }
+
+        loop {
+            let timeout = remaining(deadline);
+            match self.poll(events, Some(timeout)) {
+                Ok(()) if !events.is_empty() || timeout == Duration::from_millis(0) => {
+                    return Ok(())
+                }
+                // Returned early without any events, try again.
+                Ok(()) => continue,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/// Returns the time left until `deadline`, rounded up to whole milliseconds.
+///
+/// Some selectors truncate the timeout to milliseconds, which would make them
+/// return before `deadline`.
+fn remaining(deadline: Instant) -> Duration {
+    let timeout = deadline.saturating_duration_since(Instant::now());
+    match timeout.subsec_nanos() % 1_000_000 {
+        0 => timeout,
+        nanos => timeout + Duration::from_nanos(u64::from(1_000_000 - nanos)),
     }
 }
 
@@ -438,6 +822,10 @@ impl Registry {
     /// if the event source is deregistered from the poll instance using
     /// [`deregister`].
     ///
+    /// On Unix `Token(usize::MAX)` is reserved for posting events (see
+    /// [`Registry::post`]), registering an event source with it returns an
+    /// error.
+    ///
     /// [`event::Source`]: ./event/trait.Source.html
     /// [`poll`]: struct.Poll.html#method.poll
     /// [`reregister`]: struct.Registry.html#method.reregister
@@ -501,6 +889,7 @@ impl Registry {
             token,
             interests
         );
+        check_token(token)?;
         source.register(self, token, interests)
     }
 
@@ -569,6 +958,7 @@ impl Registry {
             token,
             interests
         );
+        check_token(token)?;
         source.reregister(self, token, interests)
     }
 
@@ -587,7 +977,10 @@ impl Registry {
     ///
     /// A handle can be passed back to `register` after it has been
     /// deregistered; however, it must be passed back to the **same** `Poll`
-    /// instance, otherwise the behavior is unspecified.
+    /// instance, otherwise the behavior is unspecified. To move a handle to a
+    /// different `Poll` instance use [`transfer`].
+    ///
+    /// [`transfer`]: Registry::transfer
     ///
     /// # Examples
     ///
@@ -631,14 +1024,242 @@ impl Registry {
         source.deregister(self)
     }
 
+    /// Pause the registration of an [`event::Source`] with the `Poll`
+    /// instance.
+    ///
+    /// While paused the event source stays registered, keeping its token and
+    /// interests, but the `Poll` instance will not return readiness events for
+    /// it. This is cheaper than deregistering and registering the event source
+    /// again, e.g. when applying flow control. Use [`resume`] to start
+    /// receiving events again.
+    ///
+    /// The event source must have previously been registered with this
+    /// instance of `Poll`. Re-registering a paused event source also resumes
+    /// it.
+    ///
+    /// [`event::Source`]: ./event/trait.Source.html
+    /// [`resume`]: Registry::resume
+    ///
+    /// # Notes
+    ///
+    /// Pausing is only supported on Unix platforms, on other platforms an
+    /// error is returned. Event sources implemented outside of Mio need to
+    /// delegate [`Source::pause`] to support pausing. [`SourceFd`] doesn't
+    /// know the token and interests it was registered with, so it can only be
+    /// paused if registrations are tracked, see [`Poll::track_registrations`].
+    ///
+    /// On platforms using epoll error and hang up events (`EPOLLERR` and
+    /// `EPOLLHUP`) can't be disabled and may still be returned for a paused
+    /// event source.
+    ///
+    /// [`Source::pause`]: ./event/trait.Source.html#method.pause
+    /// [`SourceFd`]: crate::unix::SourceFd
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(all(feature = "os-poll", feature = "net", unix), doc = "```")]
+    #[cfg_attr(
+        not(all(feature = "os-poll", feature = "net", unix)),
+        doc = "```ignore"
+    )]
+    /// # use std::error::Error;
+    /// # use std::net;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Poll, Interest, Token};
+    /// use mio::net::TcpStream;
+    /// use std::net::SocketAddr;
+    ///
+    /// let poll = Poll::new()?;
+    ///
+    /// let address: SocketAddr = "127.0.0.1:0".parse()?;
+    /// let listener = net::TcpListener::bind(address)?;
+    /// let mut socket = TcpStream::connect(listener.local_addr()?)?;
+    ///
+    /// poll.registry().register(&mut socket, Token(0), Interest::READABLE)?;
+    ///
+    /// // Stop receiving events for the socket, e.g. because our buffers are
+    /// // full.
+    /// poll.registry().pause(&mut socket)?;
+    ///
+    /// // Receive events again, using the same token and interests.
+    /// poll.registry().resume(&mut socket)?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn pause<S>(&self, source: &mut S) -> io::Result<()>
+    where
+        S: event::Source + ?Sized,
+    {
+        trace!("pausing event source registration with poller");
+        source.pause(self)
+    }
+
+    /// Resume a registration of an [`event::Source`] paused by [`pause`].
+    ///
+    /// The event source will use the token and interests it was registered
+    /// with before it was paused. Any readiness the event source gained while
+    /// it was paused will be returned by the next call to [`poll`].
+    ///
+    /// See [`pause`] for more details.
+    ///
+    /// [`event::Source`]: ./event/trait.Source.html
+    /// [`pause`]: Registry::pause
+    /// [`poll`]: Poll::poll
+    pub fn resume<S>(&self, source: &mut S) -> io::Result<()>
+    where
+        S: event::Source + ?Sized,
+    {
+        trace!("resuming event source registration with poller");
+        source.resume(self)
+    }
+
+    /// Move the registration of an [`event::Source`] from this `Registry` to
+    /// another `Registry`.
+    ///
+    /// This deregisters `source` from this `Registry` (and thus its `Poll`
+    /// instance) and registers it with `to` using the provided `token` and
+    /// `interests`. It's the only supported way of moving an event source
+    /// between `Poll` instances, e.g. to rebalance connections between worker
+    /// threads each running their own `Poll` instance.
+    ///
+    /// No readiness is lost in the move: registering an event source with the
+    /// new `Poll` instance reports the current readiness of the source, so any
+    /// readiness events that were pending for, but not yet returned by, the
+    /// old `Poll` instance will be returned by the new one. However events
+    /// that were already returned by the old `Poll` instance (e.g. still
+    /// present in an [`Events`] collection) may still be processed by the old
+    /// instance, it's up to the caller to ignore those.
+    ///
+    /// The event source must have previously been registered with this
+    /// `Registry`, otherwise the behavior is unspecified. If registering with
+    /// `to` fails, the event source is left deregistered.
+    ///
+    /// [`event::Source`]: ./event/trait.Source.html
+    /// [`Events`]: ./event/struct.Events.html
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+    #[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # use std::net;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Poll, Interest, Token};
+    /// use mio::net::TcpStream;
+    /// use std::net::SocketAddr;
+    ///
+    /// let poll1 = Poll::new()?;
+    /// let poll2 = Poll::new()?;
+    ///
+    /// let address: SocketAddr = "127.0.0.1:0".parse()?;
+    /// let listener = net::TcpListener::bind(address)?;
+    /// let mut socket = TcpStream::connect(listener.local_addr()?)?;
+    ///
+    /// poll1.registry().register(&mut socket, Token(0), Interest::READABLE)?;
+    ///
+    /// // Move the socket to the second `Poll` instance, using a new token.
+    /// poll1.registry().transfer(
+    ///     &mut socket,
+    ///     poll2.registry(),
+    ///     Token(1),
+    ///     Interest::READABLE)?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn transfer<S>(
+        &self,
+        source: &mut S,
+        to: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()>
+    where
+        S: event::Source + ?Sized,
+    {
+        trace!(
+            "transferring event source to other poller: token={:?}, interests={:?}",
+            token,
+            interests
+        );
+        source.deregister(self)?;
+        source.register(to, token, interests)
+    }
+
+    /// Post a user defined event to the `Poll` instance.
+    ///
+    /// The event, consisting of `token` and `readiness`, will be returned by
+    /// the next call to [`poll`] alongside any readiness events from the
+    /// system selector. This can be used to reschedule processing of an event
+    /// source, e.g. one that still has buffered data, without waiting for the
+    /// system selector or spinning.
+    ///
+    /// Posted events are returned in the order they were posted, before any
+    /// readiness events of the system selector. If more events are posted than
+    /// fit in [`Events`], the remaining events are returned by later calls to
+    /// [`poll`]. If any posted events are pending [`poll`] will not block.
+    ///
+    /// [`poll`]: Poll::poll
+    /// [`Events`]: ./event/struct.Events.html
+    ///
+    /// # Notes
+    ///
+    /// Posting an event wakes up a thread currently blocked in [`poll`]. On
+    /// Windows the event is posted to the I/O completion port. On Unix the
+    /// first post also registers an internal pipe with the system selector
+    /// using `Token(usize::MAX)`, which is why that token can't be used to
+    /// register event sources on Unix. Events posted while a thread is
+    /// blocked in [`poll`] are returned after the readiness events of that
+    /// call.
+    ///
+    /// The `readiness` is converted into the platform's event representation,
+    /// this is best effort: on some platforms a combination of readiness
+    /// can't be represented exactly and the returned event may contain
+    /// additional readiness, e.g. on Linux an event with write closed
+    /// readiness also has read closed readiness.
+    ///
+    /// Posting events is not supported on WASI, an error is returned.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::event::Readiness;
+    /// use mio::{Events, Poll, Token};
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(1024);
+    ///
+    /// poll.registry().post(Token(10), Readiness::READABLE)?;
+    ///
+    /// // The posted event is returned without blocking.
+    /// poll.poll(&mut events, None)?;
+    /// let event = events.iter().next().unwrap();
+    /// assert_eq!(event.token(), Token(10));
+    /// assert!(event.is_readable());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn post(&self, token: Token, readiness: Readiness) -> io::Result<()> {
+        trace!(
+            "posting event to poller: token={:?}, readiness={:?}",
+            token,
+            readiness
+        );
+        self.selector.post(token, readiness)
+    }
+
     /// Creates a new independently owned `Registry`.
     ///
     /// Event sources registered with this `Registry` will be registered with
     /// the original `Registry` and `Poll` instance.
     pub fn try_clone(&self) -> io::Result<Registry> {
-        self.selector
-            .try_clone()
-            .map(|selector| Registry { selector })
+        self.selector.try_clone().map(|selector| Registry {
+            selector,
+            #[cfg(feature = "record")]
+            recorder: self.recorder.clone(),
+        })
     }
 
     /// Internal check to ensure only a single `Waker` is active per [`Poll`]
@@ -656,6 +1277,29 @@ impl Registry {
     pub(crate) fn selector(&self) -> &sys::Selector {
         &self.selector
     }
+
+    /// Get access to the `Recorder`, used by the event sources to record
+    /// changes to their registrations.
+    #[cfg(feature = "record")]
+    #[cfg_attr(not(any(feature = "net", feature = "os-ext")), allow(dead_code))]
+    pub(crate) fn recorder(&self) -> &Recorder {
+        &self.recorder
+    }
+}
+
+/// Returns an error if `token` can't be used to register an event source.
+///
+/// On Unix `Token(usize::MAX)` is used for the waker of the posted events,
+/// events of a source registered with it would never be returned.
+pub(crate) fn check_token(token: Token) -> io::Result<()> {
+    if cfg!(unix) && token == Token(usize::MAX) {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "`Token(usize::MAX)` is reserved for posting events",
+        ))
+    } else {
+        Ok(())
+    }
 }
 
 impl fmt::Debug for Registry {
@@ -0,0 +1,539 @@
+//! Recording and replaying of event streams.
+//!
+//! Bugs in an event loop that only show up in production are hard to
+//! reproduce. To help with that a [`Poll`] instance can record every call to
+//! [`Poll::poll`], along with the changes made to its registrations, in a
+//! compact binary log, see [`Poll::start_recording`]. The log can be inspected
+//! using [`Reader`], or fed back through [`Poll::poll`] using
+//! [`Poll::replay`].
+//!
+//! [`Poll`]: crate::Poll
+//! [`Poll::poll`]: crate::Poll::poll
+//! [`Poll::start_recording`]: crate::Poll::start_recording
+//! [`Poll::replay`]: crate::Poll::replay
+//!
+//! # Log format
+//!
+//! The log starts with the 8 byte header `b"mio-log\x01"`, the last byte being
+//! the version of the format. The header is followed by the records, each
+//! starting with a one byte tag and the time the record was made, as the number
+//! of nanoseconds since the Unix epoch in a `u64`. The remainder of a record
+//! depends on its tag:
+//!
+//! | Tag | Record                 | Remainder                                         |
+//! |-----|------------------------|---------------------------------------------------|
+//! | 0   | [`Record::Poll`]       | timeout (`u64`), number of events (`u32`), events |
+//! | 1   | [`Record::Register`]   | fd (`u64`), token (`u64`), interests (`u8`)       |
+//! | 2   | [`Record::Reregister`] | fd (`u64`), token (`u64`), interests (`u8`)       |
+//! | 3   | [`Record::Deregister`] | fd (`u64`), token known (`u8`), token (`u64`)     |
+//! | 4   | [`Record::Pause`]      | fd (`u64`), token (`u64`)                         |
+//! | 5   | [`Record::Resume`]     | fd (`u64`), token (`u64`)                         |
+//!
+//! The timeout is in nanoseconds, `u64::MAX` meaning no timeout. Each event
+//! consists of its token (`u64`) followed by its readiness (`u8`). The fd is
+//! the file descriptor of the event source, or the socket or handle on
+//! Windows. If the token of a deregistered event source is not known the
+//! "token known" byte is 0 and the token is 0. All integers are little endian.
+
+use std::collections::VecDeque;
+use std::convert::TryInto;
+use std::io::{self, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{cmp, fmt};
+
+use log::error;
+
+use crate::event::{Events, OwnedEvent, Readiness};
+use crate::{sys, Interest, Token};
+
+/// Header of the log, the last byte is the version of the format.
+const HEADER: &[u8; 8] = b"mio-log\x01";
+
+const POLL: u8 = 0;
+const REGISTER: u8 = 1;
+const REREGISTER: u8 = 2;
+const DEREGISTER: u8 = 3;
+const PAUSE: u8 = 4;
+const RESUME: u8 = 5;
+
+/// Timeout used to encode no timeout.
+const NO_TIMEOUT: u64 = u64::MAX;
+
+/// A record in the log, see the [module documentation] for the format.
+///
+/// The `time` of each record is the time it was made. For a [`Poll`] record
+/// that is the time the call to [`Poll::poll`] returned.
+///
+/// [module documentation]: crate::record
+/// [`Poll`]: Record::Poll
+/// [`Poll::poll`]: crate::Poll::poll
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Record {
+    /// A successful call to [`Poll::poll`].
+    ///
+    /// [`Poll::poll`]: crate::Poll::poll
+    Poll {
+        /// Time the call returned.
+        time: SystemTime,
+        /// The timeout passed to the call.
+        timeout: Option<Duration>,
+        /// The events returned by the call.
+        events: Vec<OwnedEvent>,
+    },
+    /// An event source was registered, see [`Registry::register`].
+    ///
+    /// [`Registry::register`]: crate::Registry::register
+    Register {
+        /// Time of the registration.
+        time: SystemTime,
+        /// File descriptor of the event source.
+        fd: u64,
+        /// Token of the registration.
+        token: Token,
+        /// Interests of the registration.
+        interests: Interest,
+    },
+    /// An event source was reregistered, see [`Registry::reregister`].
+    ///
+    /// [`Registry::reregister`]: crate::Registry::reregister
+    Reregister {
+        /// Time of the registration.
+        time: SystemTime,
+        /// File descriptor of the event source.
+        fd: u64,
+        /// The new token of the registration.
+        token: Token,
+        /// The new interests of the registration.
+        interests: Interest,
+    },
+    /// An event source was deregistered, see [`Registry::deregister`].
+    ///
+    /// [`Registry::deregister`]: crate::Registry::deregister
+    Deregister {
+        /// Time of the deregistration.
+        time: SystemTime,
+        /// File descriptor of the event source.
+        fd: u64,
+        /// Token of the registration, `None` if it's not known. This is the
+        /// case for [`SourceFd`], which doesn't keep track of its token.
+        ///
+        /// [`SourceFd`]: crate::unix::SourceFd
+        token: Option<Token>,
+    },
+    /// The registration of an event source was paused, see
+    /// [`Registry::pause`].
+    ///
+    /// [`Registry::pause`]: crate::Registry::pause
+    Pause {
+        /// Time the registration was paused.
+        time: SystemTime,
+        /// File descriptor of the event source.
+        fd: u64,
+        /// Token of the registration.
+        token: Token,
+    },
+    /// The registration of an event source was resumed, see
+    /// [`Registry::resume`].
+    ///
+    /// [`Registry::resume`]: crate::Registry::resume
+    Resume {
+        /// Time the registration was resumed.
+        time: SystemTime,
+        /// File descriptor of the event source.
+        fd: u64,
+        /// Token of the registration.
+        token: Token,
+    },
+}
+
+/// Reader of a log written by a [`Poll`] instance, see
+/// [`Poll::start_recording`].
+///
+/// The `Reader` is an iterator over the [`Record`]s in the log.
+///
+/// [`Poll`]: crate::Poll
+/// [`Poll::start_recording`]: crate::Poll::start_recording
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::event::Readiness;
+/// use mio::record::{Reader, Record};
+/// use mio::{Events, Poll, Token};
+/// use std::fs::File;
+/// use std::time::Duration;
+///
+/// # let path = std::env::temp_dir().join("mio-record-reader-doc.log");
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(16);
+/// poll.start_recording(File::create(&path)?)?;
+///
+/// poll.registry().post(Token(0), Readiness::READABLE)?;
+/// poll.poll(&mut events, Some(Duration::from_millis(10)))?;
+/// poll.stop_recording()?;
+///
+/// for record in Reader::new(File::open(&path)?)? {
+///     if let Record::Poll { events, .. } = record? {
+///         assert_eq!(events[0].token(), Token(0));
+///     }
+/// }
+/// # std::fs::remove_file(path)?;
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Reader<R> {
+    log: R,
+}
+
+impl<R: Read> Reader<R> {
+    /// Create a new `Reader`, reading and checking the header of the `log`.
+    pub fn new(mut log: R) -> io::Result<Reader<R>> {
+        let mut header = [0; 8];
+        log.read_exact(&mut header)?;
+        if &header != HEADER {
+            return Err(invalid_data("invalid event log header"));
+        }
+        Ok(Reader { log })
+    }
+
+    /// Read the next record, returns `None` at the end of the log.
+    fn read_record(&mut self) -> io::Result<Option<Record>> {
+        let mut tag = [0; 1];
+        loop {
+            match self.log.read(&mut tag) {
+                Ok(0) => return Ok(None),
+                Ok(_) => break,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        let time = UNIX_EPOCH + Duration::from_nanos(self.read_u64()?);
+        let record = match tag[0] {
+            POLL => {
+                let timeout = match self.read_u64()? {
+                    NO_TIMEOUT => None,
+                    nanos => Some(Duration::from_nanos(nanos)),
+                };
+                let n = self.read_u32()?;
+                // Don't trust the length for the allocation.
+                let mut events = Vec::with_capacity(cmp::min(n, 1024) as usize);
+                for _ in 0..n {
+                    let token = Token(self.read_u64()? as usize);
+                    let readiness = Readiness::from_bits(self.read_u8()?)
+                        .ok_or_else(|| invalid_data("invalid readiness in event log"))?;
+                    events.push(OwnedEvent::new(token, readiness));
+                }
+                Record::Poll {
+                    time,
+                    timeout,
+                    events,
+                }
+            }
+            REGISTER | REREGISTER => {
+                let fd = self.read_u64()?;
+                let token = Token(self.read_u64()? as usize);
+                let interests = Interest::from_bits(self.read_u8()?)
+                    .ok_or_else(|| invalid_data("invalid interests in event log"))?;
+                if tag[0] == REGISTER {
+                    Record::Register {
+                        time,
+                        fd,
+                        token,
+                        interests,
+                    }
+                } else {
+                    Record::Reregister {
+                        time,
+                        fd,
+                        token,
+                        interests,
+                    }
+                }
+            }
+            DEREGISTER => {
+                let fd = self.read_u64()?;
+                let known = self.read_u8()?;
+                let token = Token(self.read_u64()? as usize);
+                let token = match known {
+                    0 => None,
+                    1 => Some(token),
+                    _ => return Err(invalid_data("invalid token in event log")),
+                };
+                Record::Deregister { time, fd, token }
+            }
+            PAUSE | RESUME => {
+                let fd = self.read_u64()?;
+                let token = Token(self.read_u64()? as usize);
+                if tag[0] == PAUSE {
+                    Record::Pause { time, fd, token }
+                } else {
+                    Record::Resume { time, fd, token }
+                }
+            }
+            _ => return Err(invalid_data("invalid record in event log")),
+        };
+        Ok(Some(record))
+    }
+
+    fn read_u8(&mut self) -> io::Result<u8> {
+        let mut buf = [0; 1];
+        self.log.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn read_u32(&mut self) -> io::Result<u32> {
+        let mut buf = [0; 4];
+        self.log.read_exact(&mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn read_u64(&mut self) -> io::Result<u64> {
+        let mut buf = [0; 8];
+        self.log.read_exact(&mut buf)?;
+        Ok(u64::from_le_bytes(buf))
+    }
+}
+
+impl<R: Read> Iterator for Reader<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+fn invalid_data(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Recorder shared between a [`Poll`] instance and all its `Registry`s.
+///
+/// [`Poll`]: crate::Poll
+#[derive(Clone, Default)]
+pub(crate) struct Recorder {
+    shared: Arc<Shared>,
+}
+
+#[derive(Default)]
+struct Shared {
+    /// Whether or not `log` is set, to avoid locking when not recording.
+    enabled: AtomicBool,
+    log: Mutex<Option<Log>>,
+}
+
+struct Log {
+    writer: Box<dyn Write + Send>,
+    /// Buffer used to encode a record, so it's written in a single call.
+    buf: Vec<u8>,
+}
+
+impl Recorder {
+    /// Start recording into `writer`, stopping any previous recording.
+    pub(crate) fn start(&self, mut writer: Box<dyn Write + Send>) -> io::Result<()> {
+        let mut log = self.lock();
+        if let Some(mut old) = log.take() {
+            old.writer.flush()?;
+        }
+        writer.write_all(HEADER)?;
+        *log = Some(Log {
+            writer,
+            buf: Vec::new(),
+        });
+        self.shared.enabled.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Stop recording, flushing the log.
+    pub(crate) fn stop(&self) -> io::Result<()> {
+        let mut log = self.lock();
+        self.shared.enabled.store(false, Ordering::Release);
+        match log.take() {
+            Some(mut log) => log.writer.flush(),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn poll(&self, timeout: Option<Duration>, events: &Events) {
+        self.record(POLL, |buf| {
+            let timeout = timeout.map_or(NO_TIMEOUT, |timeout| {
+                // Cap the timeout to keep `NO_TIMEOUT` unambiguous.
+                timeout.as_nanos().try_into().unwrap_or(NO_TIMEOUT - 1)
+            });
+            buf.extend_from_slice(&timeout.to_le_bytes());
+            let n_events = buf.len();
+            buf.extend_from_slice(&0u32.to_le_bytes());
+            let mut n = 0u32;
+            for event in events.iter() {
+                encode_token(buf, event.token());
+                buf.push(event.readiness().bits());
+                n += 1;
+            }
+            buf[n_events..n_events + 4].copy_from_slice(&n.to_le_bytes());
+        })
+    }
+
+    // Without `net` or `os-ext` there are no event sources to register.
+    #[cfg_attr(not(any(feature = "net", feature = "os-ext")), allow(dead_code))]
+    pub(crate) fn register(&self, fd: u64, token: Token, interests: Interest) {
+        self.record(REGISTER, |buf| {
+            buf.extend_from_slice(&fd.to_le_bytes());
+            encode_token(buf, token);
+            buf.push(interests.bits());
+        })
+    }
+
+    #[cfg_attr(not(any(feature = "net", feature = "os-ext")), allow(dead_code))]
+    pub(crate) fn reregister(&self, fd: u64, token: Token, interests: Interest) {
+        self.record(REREGISTER, |buf| {
+            buf.extend_from_slice(&fd.to_le_bytes());
+            encode_token(buf, token);
+            buf.push(interests.bits());
+        })
+    }
+
+    #[cfg_attr(not(any(feature = "net", feature = "os-ext")), allow(dead_code))]
+    pub(crate) fn deregister(&self, fd: u64, token: Option<Token>) {
+        self.record(DEREGISTER, |buf| {
+            buf.extend_from_slice(&fd.to_le_bytes());
+            buf.push(token.is_some() as u8);
+            encode_token(buf, token.unwrap_or(Token(0)));
+        })
+    }
+
+    // Only sources on Unix can be paused and resumed.
+    #[cfg_attr(
+        any(not(unix), not(any(feature = "net", feature = "os-ext"))),
+        allow(dead_code)
+    )]
+    pub(crate) fn pause(&self, fd: u64, token: Token) {
+        self.record(PAUSE, |buf| {
+            buf.extend_from_slice(&fd.to_le_bytes());
+            encode_token(buf, token);
+        })
+    }
+
+    // Only sources on Unix can be paused and resumed.
+    #[cfg_attr(
+        any(not(unix), not(any(feature = "net", feature = "os-ext"))),
+        allow(dead_code)
+    )]
+    pub(crate) fn resume(&self, fd: u64, token: Token) {
+        self.record(RESUME, |buf| {
+            buf.extend_from_slice(&fd.to_le_bytes());
+            encode_token(buf, token);
+        })
+    }
+
+    /// Write a record with `tag`, `encode` adds the remainder of the record.
+    ///
+    /// Recording never changes the result of the call being recorded, if
+    /// writing the record fails the error is logged and the recording is
+    /// stopped.
+    fn record<F>(&self, tag: u8, encode: F)
+    where
+        F: FnOnce(&mut Vec<u8>),
+    {
+        if !self.shared.enabled.load(Ordering::Acquire) {
+            return;
+        }
+
+        let mut guard = self.lock();
+        let log = match &mut *guard {
+            Some(log) => log,
+            // Stopped in the meantime.
+            None => return,
+        };
+
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos().try_into().unwrap_or(u64::MAX));
+        log.buf.clear();
+        log.buf.push(tag);
+        log.buf.extend_from_slice(&time.to_le_bytes());
+        encode(&mut log.buf);
+        if let Err(err) = log.writer.write_all(&log.buf) {
+            error!("error writing event log, stopping recording: {}", err);
+            self.shared.enabled.store(false, Ordering::Release);
+            *guard = None;
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<Log>> {
+        // The log is always left in a valid state, so we can ignore poisoning.
+        self.shared
+            .log
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn encode_token(buf: &mut Vec<u8>, token: Token) {
+    buf.extend_from_slice(&(usize::from(token) as u64).to_le_bytes());
+}
+
+impl fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder")
+            .field("enabled", &self.shared.enabled.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+/// Replays a log, used by [`Poll::replay`].
+///
+/// [`Poll::replay`]: crate::Poll::replay
+pub(crate) struct Replay {
+    reader: Reader<Box<dyn Read + Send>>,
+    /// Events of the current `Poll` record that didn't fit in `Events`.
+    pending: VecDeque<OwnedEvent>,
+}
+
+impl Replay {
+    pub(crate) fn new(reader: Reader<Box<dyn Read + Send>>) -> Replay {
+        Replay {
+            reader,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Fill `events` with the events of the next `Poll` record, skipping all
+    /// other records.
+    pub(crate) fn poll(&mut self, events: &mut Events) -> io::Result<()> {
+        events.clear();
+
+        while self.pending.is_empty() {
+            match self.reader.read_record()? {
+                Some(Record::Poll {
+                    events: recorded, ..
+                }) if recorded.is_empty() => return Ok(()),
+                Some(Record::Poll {
+                    events: recorded, ..
+                }) => self.pending.extend(recorded),
+                Some(_) => continue,
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "reached the end of the recorded event log",
+                    ))
+                }
+            }
+        }
+
+        let capacity = events.capacity();
+        let events = events.sys();
+        while events.len() < capacity {
+            match self.pending.pop_front() {
+                Some(event) => sys::event::push(events, event.token(), event.readiness()),
+                None => break,
+            }
+        }
+        Ok(())
+    }
+}
@@ -3,6 +3,9 @@ use std::io;
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::time::Duration;
 
+use crate::event::Readiness;
+use crate::Token;
+
 pub type Event = usize;
 
 pub type Events = Vec<Event>;
@@ -19,6 +22,10 @@ impl Selector {
         os_required!();
     }
 
+    pub fn post(&self, _: Token, _: Readiness) -> io::Result<()> {
+        os_required!();
+    }
+
     #[cfg(all(debug_assertions, not(target_os = "wasi")))]
     pub fn register_waker(&self) -> bool {
         os_required!();
@@ -27,7 +34,7 @@ impl Selector {
 
 #[cfg(unix)]
 cfg_any_os_ext! {
-    use crate::{Interest, Token};
+    use crate::Interest;
 
     impl Selector {
         pub fn register(&self, _: RawFd, _: Token, _: Interest) -> io::Result<()> {
@@ -41,12 +48,25 @@ cfg_any_os_ext! {
         pub fn deregister(&self, _: RawFd) -> io::Result<()> {
             os_required!();
         }
+
+        pub fn pause(&self, _: RawFd, _: Token, _: Interest) -> io::Result<()> {
+            os_required!();
+        }
+
+        pub fn resume(&self, _: RawFd, _: Token, _: Interest) -> io::Result<()> {
+            os_required!();
+        }
+
+        #[cfg(feature = "os-ext")]
+        pub fn registration(&self, _: RawFd) -> Option<(Token, Interest)> {
+            os_required!();
+        }
     }
 }
 
 #[cfg(target_os = "wasi")]
 cfg_any_os_ext! {
-    use crate::{Interest, Token};
+    use crate::Interest;
 
     impl Selector {
         pub fn register(&self, _: wasi::Fd, _: Token, _: Interest) -> io::Result<()> {
@@ -1,28 +1,279 @@
 use std::io;
 use std::net::{self, SocketAddr};
+#[cfg(not(target_os = "wasi"))]
+use std::time::Duration;
+
+cfg_send_file! {
+    use std::fs::File;
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) type TcpSocket = i32;
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn new_for_addr(_: SocketAddr) -> io::Result<TcpSocket> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn bind(_: TcpSocket, _: SocketAddr) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn connect(_: TcpSocket, _: SocketAddr) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn connect_with_data(_: TcpSocket, _: SocketAddr, _: &[u8]) -> io::Result<usize> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn listen(_: TcpSocket, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn close(_: TcpSocket) {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn local_addr(_: TcpSocket) -> io::Result<SocketAddr> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn set_reuseaddr(_: TcpSocket, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn get_reuseaddr(_: TcpSocket) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+pub(crate) fn set_reuseport(_: TcpSocket, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+pub(crate) fn get_reuseport(_: TcpSocket) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn set_send_buffer_size(_: TcpSocket, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn get_send_buffer_size(_: TcpSocket) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn set_recv_buffer_size(_: TcpSocket, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn get_recv_buffer_size(_: TcpSocket) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn set_only_v6(_: TcpSocket, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn get_only_v6(_: TcpSocket) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+pub(crate) fn set_freebind(_: TcpSocket, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+pub(crate) fn get_freebind(_: TcpSocket) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_bind_address_no_port(_: TcpSocket, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_bind_address_no_port(_: TcpSocket) -> io::Result<bool> {
+    os_required!();
+}
 
 #[cfg(not(target_os = "wasi"))]
-pub(crate) fn new_for_addr(_: SocketAddr) -> io::Result<i32> {
+pub(crate) fn set_keepalive(_: TcpSocket, _: bool) -> io::Result<()> {
     os_required!();
 }
 
 #[cfg(not(target_os = "wasi"))]
-pub(crate) fn bind(_: &net::TcpListener, _: SocketAddr) -> io::Result<()> {
+pub(crate) fn get_keepalive(_: TcpSocket) -> io::Result<bool> {
     os_required!();
 }
 
+cfg_tcp_keepalive! {
+    pub(crate) fn set_keepalive_time(_: TcpSocket, _: Duration) -> io::Result<()> {
+        os_required!();
+    }
+
+    pub(crate) fn get_keepalive_time(_: TcpSocket) -> io::Result<Duration> {
+        os_required!();
+    }
+
+    pub(crate) fn set_keepalive_interval(_: TcpSocket, _: Duration) -> io::Result<()> {
+        os_required!();
+    }
+
+    pub(crate) fn get_keepalive_interval(_: TcpSocket) -> io::Result<Duration> {
+        os_required!();
+    }
+
+    pub(crate) fn set_keepalive_retries(_: TcpSocket, _: u32) -> io::Result<()> {
+        os_required!();
+    }
+
+    pub(crate) fn get_keepalive_retries(_: TcpSocket) -> io::Result<u32> {
+        os_required!();
+    }
+}
+
 #[cfg(not(target_os = "wasi"))]
-pub(crate) fn connect(_: &net::TcpStream, _: SocketAddr) -> io::Result<()> {
+pub(crate) fn set_linger(_: TcpSocket, _: Option<Duration>) -> io::Result<()> {
     os_required!();
 }
 
 #[cfg(not(target_os = "wasi"))]
-pub(crate) fn listen(_: &net::TcpListener, _: u32) -> io::Result<()> {
+pub(crate) fn get_linger(_: TcpSocket) -> io::Result<Option<Duration>> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_user_timeout(_: TcpSocket, _: Duration) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_user_timeout(_: TcpSocket) -> io::Result<Duration> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_cork(_: TcpSocket, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_cork(_: TcpSocket) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_quickack(_: TcpSocket, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_quickack(_: TcpSocket) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_notsent_lowat(_: TcpSocket, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_notsent_lowat(_: TcpSocket) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_congestion(_: TcpSocket, _: &str) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_congestion(_: TcpSocket) -> io::Result<String> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_defer_accept(_: TcpSocket, _: Duration) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_defer_accept(_: TcpSocket) -> io::Result<Duration> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_fastopen(_: TcpSocket, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_fastopen(_: TcpSocket) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_tcp_info(_: TcpSocket) -> io::Result<crate::net::TcpInfo> {
+    os_required!();
+}
+
+cfg_send_file! {
+    pub(crate) fn send_file(_: TcpSocket, _: &File, _: u64, _: usize) -> io::Result<usize> {
+        os_required!();
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_zerocopy(_: libc::c_int, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_zerocopy(_: libc::c_int) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn send_zerocopy(_: libc::c_int, _: &[u8]) -> io::Result<usize> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_zerocopy_completion(_: libc::c_int) -> io::Result<crate::net::ZeroCopyCompletion> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn send_with_flags(_: libc::c_int, _: &[u8], _: libc::c_int) -> io::Result<usize> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn write_vectored(_: libc::c_int, _: &[std::io::IoSlice<'_>]) -> io::Result<usize> {
     os_required!();
 }
 
 #[cfg(unix)]
-pub(crate) fn set_reuseaddr(_: &net::TcpListener, _: bool) -> io::Result<()> {
+pub(crate) fn recv_with_flags(_: libc::c_int, _: &mut [u8], _: libc::c_int) -> io::Result<usize> {
     os_required!();
 }
 
@@ -1,7 +1,12 @@
 #![cfg(not(target_os = "wasi"))]
 use std::io;
+#[cfg(unix)]
+use std::io::{IoSlice, IoSliceMut};
 use std::net::{self, SocketAddr};
 
+#[cfg(target_os = "linux")]
+use crate::net::ZeroCopyCompletion;
+
 pub fn bind(_: SocketAddr) -> io::Result<net::UdpSocket> {
     os_required!()
 }
@@ -9,3 +14,118 @@ pub fn bind(_: SocketAddr) -> io::Result<net::UdpSocket> {
 pub(crate) fn only_v6(_: &net::UdpSocket) -> io::Result<bool> {
     os_required!()
 }
+
+#[cfg(unix)]
+pub(crate) fn send_with_flags(_: libc::c_int, _: &[u8], _: libc::c_int) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_with_flags(_: libc::c_int, _: &mut [u8], _: libc::c_int) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn send_to_with_flags(
+    _: libc::c_int,
+    _: &[u8],
+    _: SocketAddr,
+    _: libc::c_int,
+) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_from_with_flags(
+    _: libc::c_int,
+    _: &mut [u8],
+    _: libc::c_int,
+) -> io::Result<(usize, SocketAddr)> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn send_vectored(_: libc::c_int, _: &[IoSlice<'_>]) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_vectored(_: libc::c_int, _: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn send_to_vectored(_: libc::c_int, _: &[IoSlice<'_>], _: SocketAddr) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_from_vectored(
+    _: libc::c_int,
+    _: &mut [IoSliceMut<'_>],
+) -> io::Result<(usize, SocketAddr)> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_many(
+    _: &net::UdpSocket,
+    _: &mut [IoSliceMut<'_>],
+    _: &mut [crate::net::RecvMeta],
+) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_many(
+    _: &net::UdpSocket,
+    _: &[IoSlice<'_>],
+    _: &[SocketAddr],
+) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_gro(_: libc::c_int, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn get_gro(_: libc::c_int) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_with_meta(_: &net::UdpSocket, _: &mut [u8]) -> io::Result<crate::net::RecvMeta> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_segmented(
+    _: &net::UdpSocket,
+    _: &[u8],
+    _: Option<SocketAddr>,
+    _: u16,
+) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_zerocopy(_: libc::c_int, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_zerocopy(_: libc::c_int) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn send_zerocopy(_: libc::c_int, _: &[u8]) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_zerocopy_completion(_: libc::c_int) -> io::Result<ZeroCopyCompletion> {
+    os_required!()
+}
@@ -1,6 +1,6 @@
 pub(crate) mod datagram {
     use crate::net::SocketAddr;
-    use std::io;
+    use std::io::{self, IoSlice, IoSliceMut};
     use std::os::unix::net;
     use std::path::Path;
 
@@ -30,6 +30,54 @@ pub(crate) mod datagram {
     ) -> io::Result<(usize, SocketAddr)> {
         os_required!()
     }
+
+    pub(crate) fn send_with_flags(_: libc::c_int, _: &[u8], _: libc::c_int) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_with_flags(_: libc::c_int, _: &mut [u8], _: libc::c_int) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn send_to_with_flags(
+        _: &net::UnixDatagram,
+        _: &[u8],
+        _: &Path,
+        _: libc::c_int,
+    ) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_from_with_flags(
+        _: &net::UnixDatagram,
+        _: &mut [u8],
+        _: libc::c_int,
+    ) -> io::Result<(usize, SocketAddr)> {
+        os_required!()
+    }
+
+    pub(crate) fn send_vectored(_: libc::c_int, _: &[IoSlice<'_>]) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_vectored(_: libc::c_int, _: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn send_to_vectored(
+        _: &net::UnixDatagram,
+        _: &[IoSlice<'_>],
+        _: &Path,
+    ) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_from_vectored(
+        _: &net::UnixDatagram,
+        _: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, SocketAddr)> {
+        os_required!()
+    }
 }
 
 pub(crate) mod listener {
@@ -57,6 +105,10 @@ pub(crate) mod stream {
     use std::os::unix::net;
     use std::path::Path;
 
+    cfg_send_file! {
+        use std::fs::File;
+    }
+
     pub(crate) fn connect(_: &Path) -> io::Result<net::UnixStream> {
         os_required!()
     }
@@ -72,4 +124,22 @@ pub(crate) mod stream {
     pub(crate) fn peer_addr(_: &net::UnixStream) -> io::Result<SocketAddr> {
         os_required!()
     }
+
+    pub(crate) fn send_with_flags(_: libc::c_int, _: &[u8], _: libc::c_int) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn write_vectored(_: libc::c_int, _: &[std::io::IoSlice<'_>]) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_with_flags(_: libc::c_int, _: &mut [u8], _: libc::c_int) -> io::Result<usize> {
+        os_required!()
+    }
+
+    cfg_send_file! {
+        pub(crate) fn send_file(_: libc::c_int, _: &File, _: u64, _: usize) -> io::Result<usize> {
+            os_required!()
+        }
+    }
 }
@@ -33,6 +33,9 @@ cfg_os_poll! {
     }
 
     cfg_io_source! {
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        mod sigpipe;
+
         use std::io;
 
         // Both `kqueue` and `epoll` don't need to hold any user space state.
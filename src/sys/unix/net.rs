@@ -1,6 +1,19 @@
-use std::io;
-use std::mem::size_of;
+use std::cmp;
+use std::io::{self, IoSlice, IoSliceMut};
+use std::mem::{self, size_of};
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::ptr;
+
+#[cfg(target_os = "linux")]
+use crate::net::ZeroCopyCompletion;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::sys::unix::sigpipe;
+
+cfg_send_file! {
+    use std::convert::TryInto;
+    use std::fs::File;
+    use std::os::unix::io::AsRawFd;
+}
 
 pub(crate) fn new_ip_socket(addr: SocketAddr, socket_type: libc::c_int) -> io::Result<libc::c_int> {
     let domain = match addr {
@@ -166,3 +179,418 @@ pub(crate) unsafe fn to_socket_addr(
         _ => Err(io::ErrorKind::InvalidInput.into()),
     }
 }
+
+/// Set the socket option `name` at `level` to `value`, `T` must be the type
+/// expected by the option.
+pub(crate) fn setsockopt<T>(
+    socket: libc::c_int,
+    level: libc::c_int,
+    name: libc::c_int,
+    value: T,
+) -> io::Result<()> {
+    syscall!(setsockopt(
+        socket,
+        level,
+        name,
+        &value as *const T as *const libc::c_void,
+        size_of::<T>() as libc::socklen_t,
+    ))
+    .map(|_| ())
+}
+
+/// Get the value of socket option `name` at `level`, `T` must be the type
+/// returned by the option.
+pub(crate) fn getsockopt<T: Copy>(
+    socket: libc::c_int,
+    level: libc::c_int,
+    name: libc::c_int,
+) -> io::Result<T> {
+    // Safety: only used with plain old data types, for which zero is valid.
+    let mut value: T = unsafe { mem::zeroed() };
+    let mut len = size_of::<T>() as libc::socklen_t;
+    syscall!(getsockopt(
+        socket,
+        level,
+        name,
+        &mut value as *mut T as *mut libc::c_void,
+        &mut len,
+    ))?;
+    Ok(value)
+}
+
+/// Flags always passed to `send(2)`, `sendto(2)` and `sendmsg(2)`, to not raise
+/// `SIGPIPE` on platforms that support it. Apple platforms use `SO_NOSIGPIPE`
+/// instead, see `new_socket`.
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+pub(crate) const SEND_FLAGS: libc::c_int = libc::MSG_NOSIGNAL;
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+pub(crate) const SEND_FLAGS: libc::c_int = 0;
+
+/// Send `buf` on the connected `socket` using `send(2)` with `flags`.
+pub(crate) fn send_with_flags(
+    socket: libc::c_int,
+    buf: &[u8],
+    flags: libc::c_int,
+) -> io::Result<usize> {
+    syscall!(send(
+        socket,
+        buf.as_ptr() as *const libc::c_void,
+        buf.len(),
+        flags | SEND_FLAGS,
+    ))
+    .map(|n| n as usize)
+}
+
+/// Write `bufs` to the connected stream `socket` using `sendmsg(2)`, like
+/// `writev(2)` but without raising `SIGPIPE`.
+pub(crate) fn write_vectored(socket: libc::c_int, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+    // Limit to `IOV_MAX`, which is 1024 on all supported platforms, the
+    // remaining buffers are sent by the next write.
+    let bufs = &bufs[..cmp::min(bufs.len(), 1024)];
+    send_vectored(socket, bufs)
+}
+
+/// Send `bufs` as a single message on the connected `socket` using
+/// `sendmsg(2)`.
+pub(crate) fn send_vectored(socket: libc::c_int, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+    // Safety: null address for a connected socket.
+    unsafe { send_msg(socket, bufs, ptr::null(), 0) }
+}
+
+/// Receive a single message from the connected `socket` into `bufs` using
+/// `recvmsg(2)`.
+pub(crate) fn recv_vectored(socket: libc::c_int, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+    // Safety: null address, we're not interested in it.
+    unsafe { recv_msg(socket, bufs, ptr::null_mut(), &mut 0) }
+}
+
+/// Send `bufs` as a single message on `socket` using `sendmsg(2)`, to the
+/// address `name`, or the connected peer if `name` is null.
+///
+/// # Safety
+///
+/// `name` must be null or point to an address of `name_len` bytes.
+pub(crate) unsafe fn send_msg(
+    socket: libc::c_int,
+    bufs: &[IoSlice<'_>],
+    name: *const libc::sockaddr,
+    name_len: libc::socklen_t,
+) -> io::Result<usize> {
+    let mut msg: libc::msghdr = mem::zeroed();
+    msg.msg_name = name as *mut libc::c_void;
+    msg.msg_namelen = name_len;
+    // `IoSlice` is guaranteed to be ABI compatible with `iovec`.
+    msg.msg_iov = bufs.as_ptr() as *mut libc::iovec;
+    msg.msg_iovlen = bufs.len() as _;
+    syscall!(sendmsg(socket, &msg, SEND_FLAGS)).map(|n| n as usize)
+}
+
+/// Receive a single message from `socket` into `bufs` using `recvmsg(2)`,
+/// storing the address of the sender in `name` (unless it's null) and its
+/// length in `name_len`.
+///
+/// # Safety
+///
+/// `name` must be null or point to `name_len` bytes of writable memory.
+pub(crate) unsafe fn recv_msg(
+    socket: libc::c_int,
+    bufs: &mut [IoSliceMut<'_>],
+    name: *mut libc::sockaddr,
+    name_len: &mut libc::socklen_t,
+) -> io::Result<usize> {
+    let mut msg: libc::msghdr = mem::zeroed();
+    msg.msg_name = name as *mut libc::c_void;
+    msg.msg_namelen = *name_len;
+    // `IoSliceMut` is guaranteed to be ABI compatible with `iovec`.
+    msg.msg_iov = bufs.as_mut_ptr() as *mut libc::iovec;
+    msg.msg_iovlen = bufs.len() as _;
+    let n = syscall!(recvmsg(socket, &mut msg, 0))?;
+    *name_len = msg.msg_namelen;
+    Ok(n as usize)
+}
+
+/// Send `bufs` as a single datagram to `addr` using `sendmsg(2)`.
+pub(crate) fn send_to_vectored(
+    socket: libc::c_int,
+    bufs: &[IoSlice<'_>],
+    addr: SocketAddr,
+) -> io::Result<usize> {
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+    // Safety: `raw_addr` is `raw_addr_length` bytes.
+    unsafe { send_msg(socket, bufs, raw_addr.as_ptr(), raw_addr_length) }
+}
+
+/// Receive a single datagram into `bufs` using `recvmsg(2)`, returning the
+/// number of bytes received and the address of the sender.
+pub(crate) fn recv_from_vectored(
+    socket: libc::c_int,
+    bufs: &mut [IoSliceMut<'_>],
+) -> io::Result<(usize, SocketAddr)> {
+    // Safety: a zeroed `sockaddr_storage` is valid.
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let mut length = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    let n = unsafe {
+        recv_msg(
+            socket,
+            bufs,
+            &mut storage as *mut libc::sockaddr_storage as *mut libc::sockaddr,
+            &mut length,
+        )
+    }?;
+    // Safety: `recvmsg` initialised the address.
+    let addr = unsafe { to_socket_addr(&storage) }?;
+    Ok((n, addr))
+}
+
+/// Receive into `buf` from the connected `socket` using `recv(2)` with
+/// `flags`.
+pub(crate) fn recv_with_flags(
+    socket: libc::c_int,
+    buf: &mut [u8],
+    flags: libc::c_int,
+) -> io::Result<usize> {
+    syscall!(recv(
+        socket,
+        buf.as_mut_ptr() as *mut libc::c_void,
+        buf.len(),
+        flags,
+    ))
+    .map(|n| n as usize)
+}
+
+/// Send `buf` to `addr` using `sendto(2)` with `flags`.
+pub(crate) fn send_to_with_flags(
+    socket: libc::c_int,
+    buf: &[u8],
+    addr: SocketAddr,
+    flags: libc::c_int,
+) -> io::Result<usize> {
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+    syscall!(sendto(
+        socket,
+        buf.as_ptr() as *const libc::c_void,
+        buf.len(),
+        flags | SEND_FLAGS,
+        raw_addr.as_ptr(),
+        raw_addr_length,
+    ))
+    .map(|n| n as usize)
+}
+
+/// Receive into `buf` using `recvfrom(2)` with `flags`, returning the number
+/// of bytes received and the address of the sender.
+pub(crate) fn recv_from_with_flags(
+    socket: libc::c_int,
+    buf: &mut [u8],
+    flags: libc::c_int,
+) -> io::Result<(usize, SocketAddr)> {
+    // Safety: a zeroed `sockaddr_storage` is valid.
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let mut length = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    let n = syscall!(recvfrom(
+        socket,
+        buf.as_mut_ptr() as *mut libc::c_void,
+        buf.len(),
+        flags,
+        &mut storage as *mut libc::sockaddr_storage as *mut libc::sockaddr,
+        &mut length,
+    ))?;
+    // Safety: `recvfrom` initialised the address.
+    let addr = unsafe { to_socket_addr(&storage) }?;
+    Ok((n as usize, addr))
+}
+
+cfg_send_file! {
+    /// Send up to `len` bytes of `file`, starting at `offset`, to `socket` using
+    /// `sendfile(2)`. Returns the number of bytes sent, zero if `offset` is at (or
+    /// past) the end of the file.
+    pub(crate) fn send_file(
+        socket: libc::c_int,
+        file: &File,
+        offset: u64,
+        len: usize,
+    ) -> io::Result<usize> {
+        let offset: libc::off_t = offset
+            .try_into()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "offset too large"))?;
+        // On FreeBSD and Apple platforms a length of zero means sending the
+        // entire file.
+        if len == 0 {
+            return Ok(0);
+        }
+
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        {
+            let mut offset = offset;
+            sigpipe::suppress(|| syscall!(sendfile(socket, file.as_raw_fd(), &mut offset, len)))
+                .map(|n| n as usize)
+        }
+
+        #[cfg(target_os = "freebsd")]
+        {
+            let mut sent: libc::off_t = 0;
+            let res = syscall!(sendfile(
+                file.as_raw_fd(),
+                socket,
+                offset,
+                len,
+                ptr::null_mut(),
+                &mut sent,
+                0,
+            ));
+            partial_send(res, sent)
+        }
+
+        #[cfg(any(target_os = "ios", target_os = "macos"))]
+        {
+            let mut sent: libc::off_t = len.try_into().unwrap_or(libc::off_t::MAX);
+            let res = syscall!(sendfile(
+                file.as_raw_fd(),
+                socket,
+                offset,
+                &mut sent,
+                ptr::null_mut(),
+                0,
+            ));
+            partial_send(res, sent)
+        }
+    }
+}
+
+/// FreeBSD and Apple platforms return `EAGAIN` if the send is interrupted
+/// after sending part of the file, in which case we return the number of bytes
+/// sent.
+#[cfg(any(target_os = "freebsd", target_os = "ios", target_os = "macos"))]
+fn partial_send(res: io::Result<libc::c_int>, sent: libc::off_t) -> io::Result<usize> {
+    match res {
+        Ok(_) => Ok(sent as usize),
+        Err(ref err) if err.kind() == io::ErrorKind::WouldBlock && sent > 0 => Ok(sent as usize),
+        Err(err) => Err(err),
+    }
+}
+
+/// Move up to `len` bytes from `from` to `to` using `splice(2)`, one of which
+/// must be a pipe. Returns the number of bytes moved.
+#[cfg(all(feature = "os-ext", any(target_os = "android", target_os = "linux")))]
+pub(crate) fn splice(from: libc::c_int, to: libc::c_int, len: usize) -> io::Result<usize> {
+    sigpipe::suppress(|| {
+        syscall!(splice(
+            from,
+            ptr::null_mut(),
+            to,
+            ptr::null_mut(),
+            len,
+            libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK,
+        ))
+    })
+    .map(|n| n as usize)
+}
+
+/// `SO_ZEROCOPY` from `asm/socket.h`, not defined in libc.
+#[cfg(all(target_os = "linux", not(target_arch = "sparc64")))]
+const SO_ZEROCOPY: libc::c_int = 60;
+#[cfg(all(target_os = "linux", target_arch = "sparc64"))]
+const SO_ZEROCOPY: libc::c_int = 0x3e;
+
+/// Values for `sock_extended_err` from `linux/errqueue.h`, not defined in libc.
+#[cfg(target_os = "linux")]
+const SO_EE_ORIGIN_ZEROCOPY: u8 = 5;
+#[cfg(target_os = "linux")]
+const SO_EE_CODE_ZEROCOPY_COPIED: u8 = 1;
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_zerocopy(socket: libc::c_int, zerocopy: bool) -> io::Result<()> {
+    let val: libc::c_int = if zerocopy { 1 } else { 0 };
+    setsockopt(socket, libc::SOL_SOCKET, SO_ZEROCOPY, val)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_zerocopy(socket: libc::c_int) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket, libc::SOL_SOCKET, SO_ZEROCOPY).map(|val| val != 0)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn send_zerocopy(socket: libc::c_int, buf: &[u8]) -> io::Result<usize> {
+    syscall!(send(
+        socket,
+        buf.as_ptr() as *const libc::c_void,
+        buf.len(),
+        libc::MSG_ZEROCOPY | libc::MSG_NOSIGNAL,
+    ))
+    .map(|n| n as usize)
+}
+
+/// Read a single zerocopy completion from the error queue of `socket`.
+///
+/// Other messages on the error queue, e.g. ICMP errors, are returned as an
+/// error.
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_zerocopy_completion(socket: libc::c_int) -> io::Result<ZeroCopyCompletion> {
+    // Large enough for a single `sock_extended_err`, which is followed by the
+    // offending address for ICMP errors.
+    let mut control = [0u64; 16];
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    msg.msg_controllen = mem::size_of_val(&control) as _;
+    syscall!(recvmsg(socket, &mut msg, libc::MSG_ERRQUEUE))?;
+    if msg.msg_flags & libc::MSG_CTRUNC != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "truncated control message on error queue",
+        ));
+    }
+
+    // Safety: `msg` is initialised by `recvmsg`.
+    let mut cmsg = unsafe { libc::CMSG_FIRSTHDR(&msg) };
+    while !cmsg.is_null() {
+        // Safety: `cmsg` points into `control`.
+        let (level, kind) = unsafe { ((*cmsg).cmsg_level, (*cmsg).cmsg_type) };
+        if (level == libc::SOL_IP && kind == libc::IP_RECVERR)
+            || (level == libc::SOL_IPV6 && kind == libc::IPV6_RECVERR)
+        {
+            // Safety: the data of `IP_RECVERR` and `IPV6_RECVERR` messages is
+            // a `sock_extended_err`, which may not be aligned.
+            let err = unsafe {
+                std::ptr::read_unaligned(libc::CMSG_DATA(cmsg) as *const libc::sock_extended_err)
+            };
+            return if err.ee_errno == 0 && err.ee_origin == SO_EE_ORIGIN_ZEROCOPY {
+                Ok(ZeroCopyCompletion {
+                    start: err.ee_info,
+                    end: err.ee_data,
+                    copied: err.ee_code & SO_EE_CODE_ZEROCOPY_COPIED != 0,
+                })
+            } else if err.ee_errno != 0 {
+                Err(io::Error::from_raw_os_error(err.ee_errno as i32))
+            } else {
+                Err(unexpected_error_message())
+            };
+        }
+        // Safety: `msg` and `cmsg` are valid.
+        cmsg = unsafe { libc::CMSG_NXTHDR(&msg, cmsg) };
+    }
+    Err(unexpected_error_message())
+}
+
+#[cfg(target_os = "linux")]
+fn unexpected_error_message() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "unexpected message on error queue",
+    )
+}
@@ -207,6 +207,12 @@ pub fn new() -> io::Result<(Sender, Receiver)> {
 /// Sending end of an Unix pipe.
 ///
 /// See [`new`] for documentation, including examples.
+///
+/// On Android and Linux writing to a pipe of which the receiving end is
+/// closed returns a [`BrokenPipe`] error without raising `SIGPIPE`, even if
+/// the process doesn't ignore the signal.
+///
+/// [`BrokenPipe`]: io::ErrorKind::BrokenPipe
 #[derive(Debug)]
 pub struct Sender {
     inner: IoSource<File>,
@@ -218,6 +224,17 @@ impl Sender {
         set_nonblocking(self.inner.as_raw_fd(), nonblocking)
     }
 
+    /// Get the pending error on the `Sender`.
+    ///
+    /// Pipes don't store errors the way sockets do, so this always returns
+    /// `Ok(None)`. It exists so that code handling error events can treat
+    /// pipes and sockets alike, see [`SourceFd::take_error`].
+    ///
+    /// [`SourceFd::take_error`]: crate::unix::SourceFd::take_error
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        Ok(None)
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
@@ -309,15 +326,25 @@ impl event::Source for Sender {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.pause(registry)
+    }
+
+    fn resume(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.resume(registry)
+    }
 }
 
 impl Write for Sender {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.inner.do_io(|sender| (&*sender).write(buf))
+        self.inner
+            .do_io(|sender| suppress_sigpipe(|| (&*sender).write(buf)))
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|sender| (&*sender).write_vectored(bufs))
+        self.inner
+            .do_io(|sender| suppress_sigpipe(|| (&*sender).write_vectored(bufs)))
     }
 
     fn flush(&mut self) -> io::Result<()> {
@@ -327,11 +354,13 @@ impl Write for Sender {
 
 impl Write for &Sender {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.inner.do_io(|sender| (&*sender).write(buf))
+        self.inner
+            .do_io(|sender| suppress_sigpipe(|| (&*sender).write(buf)))
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        self.inner.do_io(|sender| (&*sender).write_vectored(bufs))
+        self.inner
+            .do_io(|sender| suppress_sigpipe(|| (&*sender).write_vectored(bufs)))
     }
 
     fn flush(&mut self) -> io::Result<()> {
@@ -383,6 +412,17 @@ impl Receiver {
         set_nonblocking(self.inner.as_raw_fd(), nonblocking)
     }
 
+    /// Get the pending error on the `Receiver`.
+    ///
+    /// Pipes don't store errors the way sockets do, so this always returns
+    /// `Ok(None)`. It exists so that code handling error events can treat
+    /// pipes and sockets alike, see [`SourceFd::take_error`].
+    ///
+    /// [`SourceFd::take_error`]: crate::unix::SourceFd::take_error
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        Ok(None)
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
@@ -474,6 +514,14 @@ impl event::Source for Receiver {
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         self.inner.deregister(registry)
     }
+
+    fn pause(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.pause(registry)
+    }
+
+    fn resume(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.resume(registry)
+    }
 }
 
 impl Read for Receiver {
@@ -536,6 +584,23 @@ impl IntoRawFd for Receiver {
     }
 }
 
+/// Call `write`, not raising `SIGPIPE` if the receiving end is closed.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn suppress_sigpipe<T, F>(write: F) -> io::Result<T>
+where
+    F: FnOnce() -> io::Result<T>,
+{
+    crate::sys::unix::sigpipe::suppress(write)
+}
+
+#[cfg(not(any(target_os = "android", target_os = "linux")))]
+fn suppress_sigpipe<T, F>(write: F) -> io::Result<T>
+where
+    F: FnOnce() -> io::Result<T>,
+{
+    write()
+}
+
 #[cfg(not(target_os = "illumos"))]
 fn set_nonblocking(fd: RawFd, nonblocking: bool) -> io::Result<()> {
     let value = nonblocking as libc::c_int;
@@ -1,3 +1,5 @@
+use super::{not_tracked, PostedEvents, Registrations, POST_TOKEN};
+use crate::event::Readiness;
 use crate::{Interest, Token};
 
 use libc::{EPOLLET, EPOLLIN, EPOLLOUT, EPOLLRDHUP};
@@ -6,7 +8,7 @@ use std::os::unix::io::{AsRawFd, RawFd};
 #[cfg(debug_assertions)]
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::time::Duration;
-use std::{cmp, i32, io, ptr};
+use std::{cmp, i32, io, mem, ptr};
 
 /// Unique id for use as `SelectorId`.
 #[cfg(debug_assertions)]
@@ -17,24 +19,20 @@ pub struct Selector {
     #[cfg(debug_assertions)]
     id: usize,
     ep: RawFd,
+    registrations: Registrations,
+    posted: PostedEvents,
     #[cfg(debug_assertions)]
     has_waker: AtomicBool,
 }
 
 impl Selector {
     pub fn new() -> io::Result<Selector> {
-        // According to libuv, `EPOLL_CLOEXEC` is not defined on Android API <
-        // 21. But `EPOLL_CLOEXEC` is an alias for `O_CLOEXEC` on that platform,
-        // so we use it instead.
-        #[cfg(target_os = "android")]
-        let flag = libc::O_CLOEXEC;
-        #[cfg(not(target_os = "android"))]
-        let flag = libc::EPOLL_CLOEXEC;
-
-        syscall!(epoll_create1(flag)).map(|ep| Selector {
+        new_epoll().map(|ep| Selector {
             #[cfg(debug_assertions)]
             id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
             ep,
+            registrations: Registrations::default(),
+            posted: PostedEvents::default(),
             #[cfg(debug_assertions)]
             has_waker: AtomicBool::new(false),
         })
@@ -46,6 +44,8 @@ impl Selector {
             #[cfg(debug_assertions)]
             id: self.id,
             ep,
+            registrations: self.registrations.clone(),
+            posted: self.posted.clone(),
             #[cfg(debug_assertions)]
             has_waker: AtomicBool::new(self.has_waker.load(Ordering::Acquire)),
         })
@@ -60,48 +60,123 @@ impl Selector {
         #[cfg(not(target_pointer_width = "32"))]
         const MAX_SAFE_TIMEOUT: u128 = libc::c_int::max_value() as u128;
 
-        let timeout = timeout
+        let mut timeout = timeout
             .map(|to| cmp::min(to.as_millis(), MAX_SAFE_TIMEOUT) as libc::c_int)
             .unwrap_or(-1);
 
         events.clear();
+
+        // Posted events are returned first, if there are any we don't want to
+        // block waiting for more.
+        let posted = self.posted.take(events.capacity());
+        if !posted.is_empty() {
+            for (token, readiness) in posted.iter() {
+                event::push(events, *token, *readiness);
+            }
+            timeout = 0;
+        }
+
+        let n_posted = events.len();
+        let room = events.capacity() - n_posted;
+        if room == 0 {
+            return Ok(());
+        }
+
         syscall!(epoll_wait(
             self.ep,
-            events.as_mut_ptr(),
-            events.capacity() as i32,
+            // This is safe because `n_posted` is at most the capacity.
+            events.as_mut_ptr().add(n_posted),
+            room as i32,
             timeout,
         ))
         .map(|n_events| {
             // This is safe because `epoll_wait` ensures that `n_events` are
             // assigned.
-            unsafe { events.set_len(n_events as usize) };
+            unsafe { events.set_len(n_posted + n_events as usize) };
+
+            if events.iter().any(|e| event::token(e) == POST_TOKEN) && self.posted.reset_waker() {
+                events.retain(|e| event::token(e) != POST_TOKEN);
+                // Return the events posted while we were waiting.
+                let room = events.capacity() - events.len();
+                for (token, readiness) in self.posted.take(room) {
+                    event::push(events, token, readiness);
+                }
+            }
+        })
+        .or_else(|err| {
+            if n_posted == 0 {
+                Err(err)
+            } else if err.kind() == io::ErrorKind::Interrupted {
+                // We still have the posted events to return.
+                Ok(())
+            } else {
+                events.clear();
+                self.posted.restore(posted);
+                Err(err)
+            }
         })
     }
 
-    pub fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
-        let mut event = libc::epoll_event {
-            events: interests_to_epoll(interests),
-            u64: usize::from(token) as u64,
-            #[cfg(target_os = "redox")]
-            _pad: 0,
-        };
+    pub fn post(&self, token: Token, readiness: Readiness) -> io::Result<()> {
+        self.posted.push(token, readiness, |fd| {
+            self.register(fd, POST_TOKEN, Interest::READABLE)
+        })
+    }
 
-        syscall!(epoll_ctl(self.ep, libc::EPOLL_CTL_ADD, fd, &mut event)).map(|_| ())
+    pub fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
+        let events = interests_to_epoll(interests);
+        epoll_ctl(self.ep, libc::EPOLL_CTL_ADD, fd, token, events)
+            .map(|()| self.registrations.insert(fd, token, interests))
     }
 
     pub fn reregister(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
-        let mut event = libc::epoll_event {
-            events: interests_to_epoll(interests),
-            u64: usize::from(token) as u64,
-            #[cfg(target_os = "redox")]
-            _pad: 0,
-        };
-
-        syscall!(epoll_ctl(self.ep, libc::EPOLL_CTL_MOD, fd, &mut event)).map(|_| ())
+        let events = interests_to_epoll(interests);
+        epoll_ctl(self.ep, libc::EPOLL_CTL_MOD, fd, token, events)
+            .map(|()| self.registrations.insert(fd, token, interests))
     }
 
     pub fn deregister(&self, fd: RawFd) -> io::Result<()> {
-        syscall!(epoll_ctl(self.ep, libc::EPOLL_CTL_DEL, fd, ptr::null_mut())).map(|_| ())
+        syscall!(epoll_ctl(self.ep, libc::EPOLL_CTL_DEL, fd, ptr::null_mut()))
+            .map(|_| self.registrations.remove(fd))
+    }
+
+    pub fn track_registrations(&self) {
+        self.registrations.enable();
+    }
+
+    pub fn after_fork(&mut self) -> io::Result<()> {
+        if !self.registrations.is_enabled() {
+            return Err(not_tracked());
+        }
+
+        let ep = new_epoll()?;
+        for (fd, registration) in self.registrations.snapshot() {
+            let events = if registration.paused {
+                EPOLLET as u32
+            } else {
+                interests_to_epoll(registration.interests)
+            };
+            match epoll_ctl(ep, libc::EPOLL_CTL_ADD, fd, registration.token, events) {
+                Ok(()) => {}
+                // The fd was closed without being deregistered first, which
+                // also removes it from the (old) epoll instance.
+                Err(ref err) if err.raw_os_error() == Some(libc::EBADF) => {
+                    self.registrations.remove(fd)
+                }
+                Err(err) => {
+                    let _ = syscall!(close(ep));
+                    return Err(err);
+                }
+            }
+        }
+
+        // The epoll instance is inherited by the child process, so we need to
+        // close our copy of it. Note that this doesn't affect the parent.
+        let old_ep = mem::replace(&mut self.ep, ep);
+        if let Err(err) = syscall!(close(old_ep)) {
+            error!("error closing epoll: {}", err);
+        }
+        Ok(())
     }
 
     #[cfg(debug_assertions)]
@@ -116,6 +191,28 @@ cfg_io_source! {
         pub fn id(&self) -> usize {
             self.id
         }
+
+        pub fn pause(&self, fd: RawFd, token: Token, _: Interest) -> io::Result<()> {
+            // An empty event mask keeps the fd registered, but still reports
+            // `EPOLLERR` and `EPOLLHUP` as those can't be disabled. We keep
+            // `EPOLLET` so that those are at least only reported once.
+            let events = EPOLLET as u32;
+            epoll_ctl(self.ep, libc::EPOLL_CTL_MOD, fd, token, events)
+                .map(|()| self.registrations.set_paused(fd, true))
+        }
+
+        pub fn resume(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
+            let events = interests_to_epoll(interests);
+            epoll_ctl(self.ep, libc::EPOLL_CTL_MOD, fd, token, events)
+                .map(|()| self.registrations.set_paused(fd, false))
+        }
+
+        /// Returns the token and interests `fd` is registered with, if
+        /// registrations are tracked. Used by `SourceFd`.
+        #[cfg(feature = "os-ext")]
+        pub fn registration(&self, fd: RawFd) -> Option<(Token, Interest)> {
+            self.registrations.get(fd)
+        }
     }
 }
 
@@ -133,6 +230,29 @@ impl Drop for Selector {
     }
 }
 
+fn new_epoll() -> io::Result<RawFd> {
+    // According to libuv, `EPOLL_CLOEXEC` is not defined on Android API <
+    // 21. But `EPOLL_CLOEXEC` is an alias for `O_CLOEXEC` on that platform,
+    // so we use it instead.
+    #[cfg(target_os = "android")]
+    let flag = libc::O_CLOEXEC;
+    #[cfg(not(target_os = "android"))]
+    let flag = libc::EPOLL_CLOEXEC;
+
+    syscall!(epoll_create1(flag))
+}
+
+fn epoll_ctl(ep: RawFd, op: libc::c_int, fd: RawFd, token: Token, events: u32) -> io::Result<()> {
+    let mut event = libc::epoll_event {
+        events,
+        u64: usize::from(token) as u64,
+        #[cfg(target_os = "redox")]
+        _pad: 0,
+    };
+
+    syscall!(epoll_ctl(ep, op, fd, &mut event)).map(|_| ())
+}
+
 fn interests_to_epoll(interests: Interest) -> u32 {
     let mut kind = EPOLLET;
 
@@ -147,15 +267,60 @@ fn interests_to_epoll(interests: Interest) -> u32 {
     kind as u32
 }
 
+/// Best effort conversion of `readiness` into epoll events, such that the
+/// functions in the `event` module return the same readiness.
+fn readiness_to_epoll(readiness: Readiness) -> u32 {
+    let mut kind = 0;
+
+    if readiness.is_readable() {
+        kind |= EPOLLIN;
+    }
+
+    if readiness.is_writable() {
+        kind |= EPOLLOUT;
+    }
+
+    if readiness.is_error() {
+        kind |= libc::EPOLLERR;
+    }
+
+    if readiness.is_read_closed() {
+        kind |= EPOLLIN | EPOLLRDHUP;
+    }
+
+    // epoll can't indicate only the write half being closed.
+    if readiness.is_write_closed() {
+        kind |= libc::EPOLLHUP;
+    }
+
+    if readiness.is_priority() {
+        kind |= libc::EPOLLPRI;
+    }
+
+    kind as u32
+}
+
 pub type Event = libc::epoll_event;
 pub type Events = Vec<Event>;
 
 pub mod event {
     use std::fmt;
 
-    use crate::sys::Event;
+    use crate::event::Readiness;
+    use crate::sys::{Event, Events};
     use crate::Token;
 
+    /// Push an event with `token` and `readiness` onto `events`, used for
+    /// posted and replayed events.
+    pub fn push(events: &mut Events, token: Token, readiness: Readiness) {
+        events.push(libc::epoll_event {
+            events: super::readiness_to_epoll(readiness),
+            u64: usize::from(token) as u64,
+            #[cfg(target_os = "redox")]
+            _pad: 0,
+        });
+    }
+
     pub fn token(event: &Event) -> Token {
         Token(event.u64 as usize)
     }
@@ -245,6 +410,6 @@ pub mod event {
 #[cfg(target_os = "android")]
 #[test]
 fn assert_close_on_exec_flag() {
-    // This assertion need to be true for Selector::new.
+    // This assertion need to be true for `new_epoll`.
     assert_eq!(libc::O_CLOEXEC, libc::EPOLL_CLOEXEC);
 }
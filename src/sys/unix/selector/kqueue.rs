@@ -1,3 +1,5 @@
+use super::{not_tracked, PostedEvents, Registrations, POST_TOKEN};
+use crate::event::Readiness;
 use crate::{Interest, Token};
 use log::error;
 use std::mem::{self, MaybeUninit};
@@ -57,21 +59,23 @@ pub struct Selector {
     #[cfg(debug_assertions)]
     id: usize,
     kq: RawFd,
+    registrations: Registrations,
+    posted: PostedEvents,
     #[cfg(debug_assertions)]
     has_waker: AtomicBool,
 }
 
 impl Selector {
     pub fn new() -> io::Result<Selector> {
-        syscall!(kqueue())
-            .and_then(|kq| syscall!(fcntl(kq, libc::F_SETFD, libc::FD_CLOEXEC)).map(|_| kq))
-            .map(|kq| Selector {
-                #[cfg(debug_assertions)]
-                id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-                kq,
-                #[cfg(debug_assertions)]
-                has_waker: AtomicBool::new(false),
-            })
+        new_kqueue().map(|kq| Selector {
+            #[cfg(debug_assertions)]
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            kq,
+            registrations: Registrations::default(),
+            posted: PostedEvents::default(),
+            #[cfg(debug_assertions)]
+            has_waker: AtomicBool::new(false),
+        })
     }
 
     pub fn try_clone(&self) -> io::Result<Selector> {
@@ -80,6 +84,8 @@ impl Selector {
             #[cfg(debug_assertions)]
             id: self.id,
             kq,
+            registrations: self.registrations.clone(),
+            posted: self.posted.clone(),
             #[cfg(debug_assertions)]
             has_waker: AtomicBool::new(self.has_waker.load(Ordering::Acquire)),
         })
@@ -94,64 +100,78 @@ impl Selector {
             // where C's long is only 32 bits.
             tv_nsec: libc::c_long::from(to.subsec_nanos() as i32),
         });
+        events.clear();
+
+        // Posted events are returned first, if there are any we don't want to
+        // block waiting for more.
+        let capacity = events.capacity();
+        let posted = self.posted.take(capacity);
+        for (token, readiness) in posted.iter() {
+            event::push(events, *token, *readiness);
+        }
+        let zero = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        let timeout = if posted.is_empty() { timeout } else { Some(zero) };
         let timeout = timeout
             .as_ref()
             .map(|s| s as *const _)
             .unwrap_or(ptr::null_mut());
 
-        events.clear();
+        let n_posted = events.len();
+        let room = capacity.saturating_sub(n_posted);
+        if room == 0 {
+            return Ok(());
+        }
+
         syscall!(kevent(
             self.kq,
             ptr::null(),
             0,
-            events.as_mut_ptr(),
-            events.capacity() as Count,
+            // This is safe because `room` is only non-zero if `n_posted` is
+            // less than the capacity.
+            events.as_mut_ptr().add(n_posted),
+            room as Count,
             timeout,
         ))
         .map(|n_events| {
             // This is safe because `kevent` ensures that `n_events` are
             // assigned.
-            unsafe { events.set_len(n_events as usize) };
+            unsafe { events.set_len(n_posted + n_events as usize) };
+
+            if events.iter().any(|e| event::token(e) == POST_TOKEN) && self.posted.reset_waker() {
+                events.retain(|e| event::token(e) != POST_TOKEN);
+                // Return the events posted while we were waiting.
+                let room = capacity - events.len();
+                for (token, readiness) in self.posted.take(room) {
+                    event::push(events, token, readiness);
+                }
+            }
+        })
+        .or_else(|err| {
+            if n_posted == 0 {
+                Err(err)
+            } else if err.kind() == io::ErrorKind::Interrupted {
+                // We still have the posted events to return.
+                Ok(())
+            } else {
+                events.clear();
+                self.posted.restore(posted);
+                Err(err)
+            }
         })
     }
 
-    pub fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
-        let flags = libc::EV_CLEAR | libc::EV_RECEIPT | libc::EV_ADD;
-        // At most we need two changes, but maybe we only need 1.
-        let mut changes: [MaybeUninit<libc::kevent>; 2] =
-            [MaybeUninit::uninit(), MaybeUninit::uninit()];
-        let mut n_changes = 0;
-
-        if interests.is_writable() {
-            let kevent = kevent!(fd, libc::EVFILT_WRITE, flags, token.0);
-            changes[n_changes] = MaybeUninit::new(kevent);
-            n_changes += 1;
-        }
-
-        if interests.is_readable() {
-            let kevent = kevent!(fd, libc::EVFILT_READ, flags, token.0);
-            changes[n_changes] = MaybeUninit::new(kevent);
-            n_changes += 1;
-        }
+    pub fn post(&self, token: Token, readiness: Readiness) -> io::Result<()> {
+        self.posted.push(token, readiness, |fd| {
+            self.register(fd, POST_TOKEN, Interest::READABLE)
+        })
+    }
 
-        // Older versions of macOS (OS X 10.11 and 10.10 have been witnessed)
-        // can return EPIPE when registering a pipe file descriptor where the
-        // other end has already disappeared. For example code that creates a
-        // pipe, closes a file descriptor, and then registers the other end will
-        // see an EPIPE returned from `register`.
-        //
-        // It also turns out that kevent will still report events on the file
-        // descriptor, telling us that it's readable/hup at least after we've
-        // done this registration. As a result we just ignore `EPIPE` here
-        // instead of propagating it.
-        //
-        // More info can be found at tokio-rs/mio#582.
-        let changes = unsafe {
-            // This is safe because we ensure that at least `n_changes` are in
-            // the array.
-            slice::from_raw_parts_mut(changes[0].as_mut_ptr(), n_changes)
-        };
-        kevent_register(self.kq, changes, &[libc::EPIPE as i64])
+    pub fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
+        register(self.kq, fd, token, interests)
+            .map(|()| self.registrations.insert(fd, token, interests))
     }
 
     pub fn reregister(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
@@ -185,6 +205,7 @@ impl Selector {
             &mut changes,
             &[libc::ENOENT as i64, libc::EPIPE as i64],
         )
+        .map(|()| self.registrations.insert(fd, token, interests))
     }
 
     pub fn deregister(&self, fd: RawFd) -> io::Result<()> {
@@ -200,6 +221,52 @@ impl Selector {
         // the filter wasn't there in first place, but we don't really care
         // about that since our goal is to remove it.
         kevent_register(self.kq, &mut changes, &[libc::ENOENT as i64])
+            .map(|()| self.registrations.remove(fd))
+    }
+
+    pub fn track_registrations(&self) {
+        self.registrations.enable();
+    }
+
+    pub fn after_fork(&mut self) -> io::Result<()> {
+        if !self.registrations.is_enabled() {
+            return Err(not_tracked());
+        }
+
+        let kq = new_kqueue()?;
+        for (fd, registration) in self.registrations.snapshot() {
+            let res = register(kq, fd, registration.token, registration.interests).and_then(|()| {
+                if registration.paused {
+                    set_enabled(kq, fd, registration.token, registration.interests, false)
+                } else {
+                    Ok(())
+                }
+            });
+            match res {
+                Ok(()) => {}
+                // The fd was closed without being deregistered first.
+                Err(ref err) if err.raw_os_error() == Some(libc::EBADF) => {
+                    self.registrations.remove(fd)
+                }
+                Err(err) => {
+                    let _ = syscall!(close(kq));
+                    return Err(err);
+                }
+            }
+        }
+
+        // A kqueue is not inherited by a child process, so unlike epoll we
+        // must not close the old fd here, it may already refer to an unrelated
+        // file.
+        self.kq = kq;
+        // The `Waker` is not backed by a file descriptor, so it's not
+        // registered with the new kqueue and must be recreated.
+        #[cfg(all(
+            debug_assertions,
+            any(target_os = "freebsd", target_os = "ios", target_os = "macos")
+        ))]
+        self.has_waker.store(false, Ordering::Release);
+        Ok(())
     }
 
     #[cfg(debug_assertions)]
@@ -248,6 +315,84 @@ impl Selector {
     }
 }
 
+fn new_kqueue() -> io::Result<RawFd> {
+    syscall!(kqueue())
+        .and_then(|kq| syscall!(fcntl(kq, libc::F_SETFD, libc::FD_CLOEXEC)).map(|_| kq))
+}
+
+/// Register `fd` with `kq`ueue.
+fn register(kq: RawFd, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
+    let flags = libc::EV_CLEAR | libc::EV_RECEIPT | libc::EV_ADD;
+    // At most we need two changes, but maybe we only need 1.
+    let mut changes: [MaybeUninit<libc::kevent>; 2] =
+        [MaybeUninit::uninit(), MaybeUninit::uninit()];
+    let mut n_changes = 0;
+
+    if interests.is_writable() {
+        let kevent = kevent!(fd, libc::EVFILT_WRITE, flags, token.0);
+        changes[n_changes] = MaybeUninit::new(kevent);
+        n_changes += 1;
+    }
+
+    if interests.is_readable() {
+        let kevent = kevent!(fd, libc::EVFILT_READ, flags, token.0);
+        changes[n_changes] = MaybeUninit::new(kevent);
+        n_changes += 1;
+    }
+
+    // Older versions of macOS (OS X 10.11 and 10.10 have been witnessed)
+    // can return EPIPE when registering a pipe file descriptor where the
+    // other end has already disappeared. For example code that creates a
+    // pipe, closes a file descriptor, and then registers the other end will
+    // see an EPIPE returned from `register`.
+    //
+    // It also turns out that kevent will still report events on the file
+    // descriptor, telling us that it's readable/hup at least after we've
+    // done this registration. As a result we just ignore `EPIPE` here
+    // instead of propagating it.
+    //
+    // More info can be found at tokio-rs/mio#582.
+    let changes = unsafe {
+        // This is safe because we ensure that at least `n_changes` are in
+        // the array.
+        slice::from_raw_parts_mut(changes[0].as_mut_ptr(), n_changes)
+    };
+    kevent_register(kq, changes, &[libc::EPIPE as i64])
+}
+
+/// Enable or disable the filters of `fd` in `kq`ueue, without removing them.
+fn set_enabled(
+    kq: RawFd,
+    fd: RawFd,
+    token: Token,
+    interests: Interest,
+    enable: bool,
+) -> io::Result<()> {
+    let flags = libc::EV_RECEIPT | if enable { libc::EV_ENABLE } else { libc::EV_DISABLE };
+    let mut changes: [MaybeUninit<libc::kevent>; 2] =
+        [MaybeUninit::uninit(), MaybeUninit::uninit()];
+    let mut n_changes = 0;
+
+    if interests.is_writable() {
+        let kevent = kevent!(fd, libc::EVFILT_WRITE, flags, token.0);
+        changes[n_changes] = MaybeUninit::new(kevent);
+        n_changes += 1;
+    }
+
+    if interests.is_readable() {
+        let kevent = kevent!(fd, libc::EVFILT_READ, flags, token.0);
+        changes[n_changes] = MaybeUninit::new(kevent);
+        n_changes += 1;
+    }
+
+    let changes = unsafe {
+        // This is safe because we ensure that at least `n_changes` are in
+        // the array.
+        slice::from_raw_parts_mut(changes[0].as_mut_ptr(), n_changes)
+    };
+    kevent_register(kq, changes, &[])
+}
+
 /// Register `changes` with `kq`ueue.
 fn kevent_register(
     kq: RawFd,
@@ -292,11 +437,28 @@ fn check_errors(events: &[libc::kevent], ignored_errors: &[i64]) -> io::Result<(
 }
 
 cfg_io_source! {
-    #[cfg(debug_assertions)]
     impl Selector {
+        #[cfg(debug_assertions)]
         pub fn id(&self) -> usize {
             self.id
         }
+
+        pub fn pause(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
+            set_enabled(self.kq, fd, token, interests, false)
+                .map(|()| self.registrations.set_paused(fd, true))
+        }
+
+        pub fn resume(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
+            set_enabled(self.kq, fd, token, interests, true)
+                .map(|()| self.registrations.set_paused(fd, false))
+        }
+
+        /// Returns the token and interests `fd` is registered with, if
+        /// registrations are tracked. Used by `SourceFd`.
+        #[cfg(feature = "os-ext")]
+        pub fn registration(&self, fd: RawFd) -> Option<(Token, Interest)> {
+            self.registrations.get(fd)
+        }
     }
 }
 
@@ -347,18 +509,41 @@ unsafe impl Sync for Events {}
 
 pub mod event {
     use std::fmt;
+    use std::mem;
 
-    use crate::sys::Event;
+    use crate::event::Readiness;
+    use crate::sys::{Event, Events};
     use crate::Token;
 
-    use super::{Filter, Flags};
+    use super::{Filter, Flags, UData};
+
+    /// Filter used for posted and replayed events, not used by the kernel
+    /// (all its filters are negative). The readiness is stored in `fflags`.
+    const POSTED: Filter = 0;
+
+    /// Push a `kevent` for a posted or replayed event, such that the
+    /// functions in this module return `readiness`.
+    pub fn push(events: &mut Events, token: Token, readiness: Readiness) {
+        let mut kevent = kevent!(0, POSTED, 0, token.0);
+        kevent.fflags = readiness.bits().into();
+        events.push(kevent);
+    }
+
+    /// Returns the readiness of a posted or replayed event.
+    fn posted(event: &Event) -> Readiness {
+        if event.filter == POSTED {
+            Readiness::from_bits(event.fflags as u8).unwrap_or(Readiness::EMPTY)
+        } else {
+            Readiness::EMPTY
+        }
+    }
 
     pub fn token(event: &Event) -> Token {
         Token(event.udata as usize)
     }
 
     pub fn is_readable(event: &Event) -> bool {
-        event.filter == libc::EVFILT_READ || {
+        event.filter == libc::EVFILT_READ || posted(event).is_readable() || {
             #[cfg(any(target_os = "freebsd", target_os = "ios", target_os = "macos"))]
             // Used by the `Awakener`. On platforms that use `eventfd` or a unix
             // pipe it will emit a readable event so we'll fake that here as
@@ -374,27 +559,31 @@ pub mod event {
     }
 
     pub fn is_writable(event: &Event) -> bool {
-        event.filter == libc::EVFILT_WRITE
+        event.filter == libc::EVFILT_WRITE || posted(event).is_writable()
     }
 
     pub fn is_error(event: &Event) -> bool {
         (event.flags & libc::EV_ERROR) != 0 ||
             // When the read end of the socket is closed, EV_EOF is set on
             // flags, and fflags contains the error if there is one.
-            (event.flags & libc::EV_EOF) != 0 && event.fflags != 0
+            (event.flags & libc::EV_EOF) != 0 && event.fflags != 0 ||
+            posted(event).is_error()
     }
 
     pub fn is_read_closed(event: &Event) -> bool {
         event.filter == libc::EVFILT_READ && event.flags & libc::EV_EOF != 0
+            || posted(event).is_read_closed()
     }
 
     pub fn is_write_closed(event: &Event) -> bool {
         event.filter == libc::EVFILT_WRITE && event.flags & libc::EV_EOF != 0
+            || posted(event).is_write_closed()
     }
 
-    pub fn is_priority(_: &Event) -> bool {
-        // kqueue doesn't have priority indicators.
-        false
+    pub fn is_priority(event: &Event) -> bool {
+        // kqueue doesn't have priority indicators, only posted events can
+        // have it.
+        posted(event).is_priority()
     }
 
     #[allow(unused_variables)] // `event` is not used on some platforms.
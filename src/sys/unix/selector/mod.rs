@@ -1,3 +1,12 @@
+use crate::event::Readiness;
+use crate::{Interest, Token};
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::os::unix::io::{FromRawFd, RawFd};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
 #[cfg(any(
     target_os = "android",
     target_os = "illumos",
@@ -43,3 +52,277 @@ pub(crate) use self::kqueue::{event, Event, Events, Selector};
 /// could result in some interesting and unexpected errors. Avoid that by using
 /// an fd that doesn't have a pre-determined usage.
 const LOWEST_FD: libc::c_int = 3;
+
+/// Registrations made with a `Selector`, used to recreate the selector in
+/// `Selector::after_fork`.
+///
+/// Tracking is disabled by default, as it requires locking on every
+/// (re|de)registration, and enabled using `Selector::track_registrations`.
+///
+/// This is shared between all clones of a `Selector`, as they all refer to the
+/// same underlying OS selector.
+#[derive(Clone, Debug, Default)]
+struct Registrations {
+    inner: Arc<RegistrationsInner>,
+}
+
+#[derive(Debug, Default)]
+struct RegistrationsInner {
+    enabled: AtomicBool,
+    registrations: Mutex<HashMap<RawFd, Registration>>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Registration {
+    token: Token,
+    interests: Interest,
+    /// Whether or not the registration is paused, see `Selector::pause`.
+    paused: bool,
+}
+
+impl Registrations {
+    /// Start tracking registrations, registrations made before this is
+    /// called are not tracked.
+    fn enable(&self) {
+        self.inner.enabled.store(true, Ordering::Release);
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.inner.enabled.load(Ordering::Acquire)
+    }
+
+    /// Add, or update, the registration of `fd`. This also resumes the
+    /// registration if it was paused.
+    fn insert(&self, fd: RawFd, token: Token, interests: Interest) {
+        if self.is_enabled() {
+            let registration = Registration {
+                token,
+                interests,
+                paused: false,
+            };
+            self.lock().insert(fd, registration);
+        }
+    }
+
+    /// Remove the registration of `fd`.
+    fn remove(&self, fd: RawFd) {
+        if self.is_enabled() {
+            self.lock().remove(&fd);
+        }
+    }
+
+    /// Returns a copy of all current registrations.
+    fn snapshot(&self) -> Vec<(RawFd, Registration)> {
+        self.lock()
+            .iter()
+            .map(|(fd, registration)| (*fd, *registration))
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<RawFd, Registration>> {
+        lock(&self.inner.registrations)
+    }
+}
+
+cfg_io_source! {
+    impl Registrations {
+        /// Returns the token and interests `fd` is registered with, if it's
+        /// tracked.
+        #[cfg(feature = "os-ext")]
+        fn get(&self, fd: RawFd) -> Option<(Token, Interest)> {
+            if self.is_enabled() {
+                self.lock()
+                    .get(&fd)
+                    .map(|registration| (registration.token, registration.interests))
+            } else {
+                None
+            }
+        }
+
+        /// Mark the registration of `fd` as (not) paused.
+        fn set_paused(&self, fd: RawFd, paused: bool) {
+            if self.is_enabled() {
+                if let Some(registration) = self.lock().get_mut(&fd) {
+                    registration.paused = paused;
+                }
+            }
+        }
+    }
+}
+
+/// Error returned by `Selector::after_fork` if registrations are not tracked.
+fn not_tracked() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "registrations are not tracked, see `Poll::track_registrations`",
+    )
+}
+
+/// Token used to register the waker of `PostedEvents` with the selector.
+/// Events with this token are not returned by `Selector::select`.
+const POST_TOKEN: Token = Token(usize::MAX);
+
+/// Events posted using `Selector::post`, returned by the next call to
+/// `Selector::select`.
+///
+/// Like `Registrations` this is shared between all clones of a `Selector`.
+#[derive(Clone, Debug, Default)]
+struct PostedEvents {
+    inner: Arc<PostedInner>,
+}
+
+#[derive(Debug, Default)]
+struct PostedInner {
+    /// Whether or not `events` is non-empty, so that `select` doesn't have to
+    /// lock `events` if nothing was posted.
+    pending: AtomicBool,
+    events: Mutex<VecDeque<(Token, Readiness)>>,
+    /// Waker used to wake up a blocked `select` call, created by the first
+    /// call to `push`.
+    waker: Mutex<Option<PostWaker>>,
+}
+
+impl PostedEvents {
+    /// Add an event to the back of the queue, waking up the thread blocked in
+    /// `select` (if any). `register` is used to register the waker with the
+    /// selector the first time.
+    ///
+    /// If an error is returned the event is still posted.
+    fn push<F>(&self, token: Token, readiness: Readiness, register: F) -> io::Result<()>
+    where
+        F: FnOnce(RawFd) -> io::Result<()>,
+    {
+        let was_empty = {
+            let mut events = self.lock();
+            events.push_back((token, readiness));
+            self.inner.pending.store(true, Ordering::Release);
+            events.len() == 1
+        };
+
+        // If there already were events posted we already woke up `select`.
+        if was_empty {
+            let mut waker = lock(&self.inner.waker);
+            if waker.is_none() {
+                *waker = Some(PostWaker::new(register)?);
+            }
+            waker.as_ref().unwrap().wake()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Removes at most `max` events from the front of the queue.
+    fn take(&self, max: usize) -> Vec<(Token, Readiness)> {
+        if !self.inner.pending.load(Ordering::Acquire) {
+            return Vec::new();
+        }
+
+        let mut posted = self.lock();
+        let n = posted.len().min(max);
+        let events = posted.drain(..n).collect();
+        self.inner
+            .pending
+            .store(!posted.is_empty(), Ordering::Release);
+        events
+    }
+
+    /// Put events previously removed using `take` back at the front of the
+    /// queue.
+    fn restore(&self, events: Vec<(Token, Readiness)>) {
+        let mut posted = self.lock();
+        for event in events.into_iter().rev() {
+            posted.push_front(event);
+        }
+        self.inner
+            .pending
+            .store(!posted.is_empty(), Ordering::Release);
+    }
+
+    /// Reset the waker after `select` returned an event with `POST_TOKEN`.
+    /// Returns false if the waker doesn't exist, i.e. the event was for a
+    /// different event source using the same token.
+    fn reset_waker(&self) -> bool {
+        match &*lock(&self.inner.waker) {
+            Some(waker) => {
+                waker.reset();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<(Token, Readiness)>> {
+        lock(&self.inner.events)
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // A panic while holding the lock can't leave the data in an inconsistent
+    // state, so we can ignore the poisoning.
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Waker for `PostedEvents`, backed by a unix pipe.
+///
+/// We can't use `Waker` as on some platforms it's backed by kqueue's
+/// `EVFILT_USER`, which would overwrite the user's `Waker`.
+#[derive(Debug)]
+struct PostWaker {
+    sender: File,
+    receiver: File,
+}
+
+impl PostWaker {
+    fn new<F>(register: F) -> io::Result<PostWaker>
+    where
+        F: FnOnce(RawFd) -> io::Result<()>,
+    {
+        let mut fds: [RawFd; 2] = [-1, -1];
+
+        #[cfg(not(any(target_os = "ios", target_os = "macos")))]
+        syscall!(pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC | libc::O_NONBLOCK))?;
+
+        // For platforms that don't have `pipe2(2)` we need to manually set the
+        // correct flags on the file descriptor.
+        #[cfg(any(target_os = "ios", target_os = "macos"))]
+        syscall!(pipe(fds.as_mut_ptr()))?;
+
+        // Turn the file descriptors into files first so we're ensured they're
+        // closed when dropped, e.g. when register below fails.
+        let sender = unsafe { File::from_raw_fd(fds[1]) };
+        let receiver = unsafe { File::from_raw_fd(fds[0]) };
+
+        #[cfg(any(target_os = "ios", target_os = "macos"))]
+        for fd in &fds {
+            syscall!(fcntl(*fd, libc::F_SETFL, libc::O_NONBLOCK))?;
+            syscall!(fcntl(*fd, libc::F_SETFD, libc::FD_CLOEXEC))?;
+        }
+
+        register(fds[0]).map(|()| PostWaker { sender, receiver })
+    }
+
+    fn wake(&self) -> io::Result<()> {
+        // The epoll emulation on some illumos systems requires the pipe
+        // buffer to be completely empty for an edge-triggered wakeup.
+        #[cfg(target_os = "illumos")]
+        self.reset();
+
+        match (&self.sender).write(&[1]) {
+            Ok(_) => Ok(()),
+            // The pipe is full, so `select` will be woken up.
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => Ok(()),
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => self.wake(),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Empty the pipe's buffer, ignoring any errors.
+    fn reset(&self) {
+        let mut buf = [0; 64];
+        while let Ok(n) = (&self.receiver).read(&mut buf) {
+            if n == 0 {
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::{io, mem, ptr};
+
+/// Call `write` without raising `SIGPIPE` for the process, for writes that
+/// can't use `MSG_NOSIGNAL`, such as writes to pipes.
+///
+/// If `SIGPIPE` is ignored, as it is by default for Rust programs, `write` is
+/// called directly. Otherwise `SIGPIPE` is blocked for the calling thread
+/// while calling `write`. If the write fails with `EPIPE` the signal it
+/// generated is consumed, unless the signal was already pending, before the
+/// signal mask is restored.
+pub(crate) fn suppress<T, F>(write: F) -> io::Result<T>
+where
+    F: FnOnce() -> io::Result<T>,
+{
+    if is_ignored() {
+        return write();
+    }
+
+    // Safety: `sigset_t` is plain old data, it's initialised by `sigemptyset`.
+    let mut sigpipe: libc::sigset_t = unsafe { mem::zeroed() };
+    let mut old_mask: libc::sigset_t = unsafe { mem::zeroed() };
+    let mut pending: libc::sigset_t = unsafe { mem::zeroed() };
+    unsafe {
+        libc::sigemptyset(&mut sigpipe);
+        libc::sigaddset(&mut sigpipe, libc::SIGPIPE);
+    }
+
+    // NOTE: `pthread_sigmask` returns the error, rather than setting `errno`.
+    let res = unsafe { libc::pthread_sigmask(libc::SIG_BLOCK, &sigpipe, &mut old_mask) };
+    if res != 0 {
+        return Err(io::Error::from_raw_os_error(res));
+    }
+    // If we can't determine whether a `SIGPIPE` is already pending assume it
+    // is, so we don't consume a signal we didn't generate.
+    let was_pending = unsafe {
+        libc::sigpending(&mut pending) == -1 || libc::sigismember(&pending, libc::SIGPIPE) == 1
+    };
+
+    let result = write();
+
+    let is_epipe = match &result {
+        Err(err) => err.raw_os_error() == Some(libc::EPIPE),
+        Ok(_) => false,
+    };
+    if is_epipe && !was_pending {
+        let timeout = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        // Fails with `EAGAIN` if no signal was generated, e.g. if `SIGPIPE`
+        // is ignored, which is fine.
+        while unsafe { libc::sigtimedwait(&sigpipe, ptr::null_mut(), &timeout) } == -1
+            && io::Error::last_os_error().kind() == io::ErrorKind::Interrupted
+        {}
+    }
+
+    unsafe { libc::pthread_sigmask(libc::SIG_SETMASK, &old_mask, ptr::null_mut()) };
+    result
+}
+
+/// Returns true if `SIGPIPE` is ignored.
+///
+/// This is checked on every call, as the process may change the disposition
+/// of the signal at any time.
+fn is_ignored() -> bool {
+    // Safety: `sigaction` is plain old data, it's initialised by the call to
+    // `sigaction`.
+    let mut action: libc::sigaction = unsafe { mem::zeroed() };
+    if unsafe { libc::sigaction(libc::SIGPIPE, ptr::null(), &mut action) } == -1 {
+        // Block the signal to be safe.
+        return false;
+    }
+    action.sa_sigaction == libc::SIG_IGN
+}
@@ -1,6 +1,8 @@
 use crate::{event, Interest, Registry, Token};
 
 use std::io;
+#[cfg(feature = "os-ext")]
+use std::mem;
 use std::os::unix::io::RawFd;
 
 /// Adapter for [`RawFd`] providing an [`event::Source`] implementation.
@@ -91,6 +93,36 @@ use std::os::unix::io::RawFd;
 #[derive(Debug)]
 pub struct SourceFd<'a>(pub &'a RawFd);
 
+impl<'a> SourceFd<'a> {
+    /// Get the value of the `SO_ERROR` option on the FD.
+    ///
+    /// This will retrieve the stored error in the underlying socket, clearing
+    /// the field in the process. This is the error reported by an event for
+    /// which [`Event::is_error`] returns `true`.
+    ///
+    /// FDs that are not sockets, such as pipes, don't have a pending error and
+    /// always return `Ok(None)`.
+    ///
+    /// [`Event::is_error`]: ../event/struct.Event.html#method.is_error
+    #[cfg(feature = "os-ext")]
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        let mut err: libc::c_int = 0;
+        let mut len = mem::size_of::<libc::c_int>() as libc::socklen_t;
+        match syscall!(getsockopt(
+            *self.0,
+            libc::SOL_SOCKET,
+            libc::SO_ERROR,
+            &mut err as *mut _ as *mut libc::c_void,
+            &mut len,
+        )) {
+            Ok(_) if err == 0 => Ok(None),
+            Ok(_) => Ok(Some(io::Error::from_raw_os_error(err))),
+            Err(ref err) if err.raw_os_error() == Some(libc::ENOTSOCK) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
 impl<'a> event::Source for SourceFd<'a> {
     fn register(
         &mut self,
@@ -98,7 +130,10 @@ impl<'a> event::Source for SourceFd<'a> {
         token: Token,
         interests: Interest,
     ) -> io::Result<()> {
-        registry.selector().register(*self.0, token, interests)
+        registry.selector().register(*self.0, token, interests)?;
+        #[cfg(feature = "record")]
+        registry.recorder().register(*self.0 as u64, token, interests);
+        Ok(())
     }
 
     fn reregister(
@@ -107,10 +142,48 @@ impl<'a> event::Source for SourceFd<'a> {
         token: Token,
         interests: Interest,
     ) -> io::Result<()> {
-        registry.selector().reregister(*self.0, token, interests)
+        registry.selector().reregister(*self.0, token, interests)?;
+        #[cfg(feature = "record")]
+        registry.recorder().reregister(*self.0 as u64, token, interests);
+        Ok(())
     }
 
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
-        registry.selector().deregister(*self.0)
+        registry.selector().deregister(*self.0)?;
+        // `SourceFd` doesn't know its token.
+        #[cfg(feature = "record")]
+        registry.recorder().deregister(*self.0 as u64, None);
+        Ok(())
+    }
+
+    // `SourceFd` can only be used outside of Mio with the `os-ext` feature.
+    #[cfg(feature = "os-ext")]
+    fn pause(&mut self, registry: &Registry) -> io::Result<()> {
+        let (token, interests) = tracked_registration(registry, *self.0)?;
+        registry.selector().pause(*self.0, token, interests)?;
+        #[cfg(feature = "record")]
+        registry.recorder().pause(*self.0 as u64, token);
+        Ok(())
     }
+
+    #[cfg(feature = "os-ext")]
+    fn resume(&mut self, registry: &Registry) -> io::Result<()> {
+        let (token, interests) = tracked_registration(registry, *self.0)?;
+        registry.selector().resume(*self.0, token, interests)?;
+        #[cfg(feature = "record")]
+        registry.recorder().resume(*self.0 as u64, token);
+        Ok(())
+    }
+}
+
+/// `SourceFd` doesn't hold any state, so to pause and resume it we need the
+/// registrations tracked by the selector.
+#[cfg(feature = "os-ext")]
+fn tracked_registration(registry: &Registry, fd: RawFd) -> io::Result<(Token, Interest)> {
+    registry.selector().registration(fd).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "I/O source not registered with `Registry`, or registrations are not tracked",
+        )
+    })
 }
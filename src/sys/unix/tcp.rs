@@ -3,10 +3,25 @@ use std::io;
 use std::mem::{size_of, MaybeUninit};
 use std::net::{self, SocketAddr};
 use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::time::Duration;
 
-use crate::sys::unix::net::{new_socket, socket_addr, to_socket_addr};
+#[cfg(target_os = "linux")]
+use crate::net::TcpInfo;
+use crate::sys::unix::net::{getsockopt, new_socket, setsockopt, socket_addr, to_socket_addr};
+pub(crate) use crate::sys::unix::net::{recv_with_flags, send_with_flags, write_vectored};
+cfg_send_file! {
+    pub(crate) use crate::sys::unix::net::send_file;
+}
+#[cfg(all(feature = "os-ext", any(target_os = "android", target_os = "linux")))]
+pub(crate) use crate::sys::unix::net::splice;
+#[cfg(target_os = "linux")]
+pub(crate) use crate::sys::unix::net::{
+    get_zerocopy, recv_zerocopy_completion, send_zerocopy, set_zerocopy,
+};
+
+pub(crate) type TcpSocket = libc::c_int;
 
-pub(crate) fn new_for_addr(address: SocketAddr) -> io::Result<libc::c_int> {
+pub(crate) fn new_for_addr(address: SocketAddr) -> io::Result<TcpSocket> {
     let domain = match address {
         SocketAddr::V4(_) => libc::AF_INET,
         SocketAddr::V6(_) => libc::AF_INET6,
@@ -14,41 +29,433 @@ pub(crate) fn new_for_addr(address: SocketAddr) -> io::Result<libc::c_int> {
     new_socket(domain, libc::SOCK_STREAM)
 }
 
-pub(crate) fn bind(socket: &net::TcpListener, addr: SocketAddr) -> io::Result<()> {
+pub(crate) fn bind(socket: TcpSocket, addr: SocketAddr) -> io::Result<()> {
     let (raw_addr, raw_addr_length) = socket_addr(&addr);
-    syscall!(bind(socket.as_raw_fd(), raw_addr.as_ptr(), raw_addr_length))?;
+    syscall!(bind(socket, raw_addr.as_ptr(), raw_addr_length))?;
     Ok(())
 }
 
-pub(crate) fn connect(socket: &net::TcpStream, addr: SocketAddr) -> io::Result<()> {
+pub(crate) fn connect(socket: TcpSocket, addr: SocketAddr) -> io::Result<()> {
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+
+    match syscall!(connect(socket, raw_addr.as_ptr(), raw_addr_length)) {
+        Err(err) if err.raw_os_error() != Some(libc::EINPROGRESS) => Err(err),
+        _ => Ok(()),
+    }
+}
+
+/// Connect using TCP Fast Open, sending `data` with the SYN packet if a
+/// cookie for `addr` is available. Returns the number of bytes sent with the
+/// SYN, which may be zero.
+#[cfg(target_os = "linux")]
+pub(crate) fn connect_with_data(
+    socket: TcpSocket,
+    addr: SocketAddr,
+    data: &[u8],
+) -> io::Result<usize> {
     let (raw_addr, raw_addr_length) = socket_addr(&addr);
 
-    match syscall!(connect(
-        socket.as_raw_fd(),
+    match syscall!(sendto(
+        socket,
+        data.as_ptr() as *const libc::c_void,
+        data.len(),
+        libc::MSG_FASTOPEN,
         raw_addr.as_ptr(),
         raw_addr_length
     )) {
-        Err(err) if err.raw_os_error() != Some(libc::EINPROGRESS) => Err(err),
-        _ => Ok(()),
+        Ok(n) => Ok(n as usize),
+        // No cookie was available, only the SYN was sent.
+        Err(err) if err.raw_os_error() == Some(libc::EINPROGRESS) => Ok(0),
+        Err(err) => Err(err),
     }
 }
 
-pub(crate) fn listen(socket: &net::TcpListener, backlog: u32) -> io::Result<()> {
+pub(crate) fn listen(socket: TcpSocket, backlog: u32) -> io::Result<()> {
     let backlog = backlog.try_into().unwrap_or(i32::max_value());
-    syscall!(listen(socket.as_raw_fd(), backlog))?;
+    syscall!(listen(socket, backlog))?;
     Ok(())
 }
 
-pub(crate) fn set_reuseaddr(socket: &net::TcpListener, reuseaddr: bool) -> io::Result<()> {
+pub(crate) fn close(socket: TcpSocket) {
+    let _ = unsafe { libc::close(socket) };
+}
+
+pub(crate) fn local_addr(socket: TcpSocket) -> io::Result<SocketAddr> {
+    let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::uninit();
+    let mut length = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    syscall!(getsockname(socket, addr.as_mut_ptr() as *mut _, &mut length))?;
+    // This is safe because `getsockname` initialised the address.
+    unsafe { to_socket_addr(addr.as_ptr()) }
+}
+
+pub(crate) fn set_reuseaddr(socket: TcpSocket, reuseaddr: bool) -> io::Result<()> {
     let val: libc::c_int = if reuseaddr { 1 } else { 0 };
+    setsockopt(socket, libc::SOL_SOCKET, libc::SO_REUSEADDR, val)
+}
+
+pub(crate) fn get_reuseaddr(socket: TcpSocket) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket, libc::SOL_SOCKET, libc::SO_REUSEADDR).map(|val| val != 0)
+}
+
+#[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn set_reuseport(socket: TcpSocket, reuseport: bool) -> io::Result<()> {
+    let val: libc::c_int = if reuseport { 1 } else { 0 };
+    setsockopt(socket, libc::SOL_SOCKET, libc::SO_REUSEPORT, val)
+}
+
+#[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+pub(crate) fn get_reuseport(socket: TcpSocket) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket, libc::SOL_SOCKET, libc::SO_REUSEPORT).map(|val| val != 0)
+}
+
+pub(crate) fn set_send_buffer_size(socket: TcpSocket, size: u32) -> io::Result<()> {
+    let size = size.try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(socket, libc::SOL_SOCKET, libc::SO_SNDBUF, size)
+}
+
+pub(crate) fn get_send_buffer_size(socket: TcpSocket) -> io::Result<u32> {
+    getsockopt::<libc::c_int>(socket, libc::SOL_SOCKET, libc::SO_SNDBUF).map(|size| size as u32)
+}
+
+pub(crate) fn set_recv_buffer_size(socket: TcpSocket, size: u32) -> io::Result<()> {
+    let size = size.try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(socket, libc::SOL_SOCKET, libc::SO_RCVBUF, size)
+}
+
+pub(crate) fn get_recv_buffer_size(socket: TcpSocket) -> io::Result<u32> {
+    getsockopt::<libc::c_int>(socket, libc::SOL_SOCKET, libc::SO_RCVBUF).map(|size| size as u32)
+}
+
+pub(crate) fn set_only_v6(socket: TcpSocket, only_v6: bool) -> io::Result<()> {
+    let val: libc::c_int = if only_v6 { 1 } else { 0 };
+    setsockopt(socket, libc::IPPROTO_IPV6, libc::IPV6_V6ONLY, val)
+}
+
+pub(crate) fn get_only_v6(socket: TcpSocket) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket, libc::IPPROTO_IPV6, libc::IPV6_V6ONLY).map(|val| val != 0)
+}
+
+#[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+pub(crate) fn set_freebind(socket: TcpSocket, freebind: bool) -> io::Result<()> {
+    let val: libc::c_int = if freebind { 1 } else { 0 };
+    setsockopt(socket, libc::IPPROTO_IP, libc::IP_FREEBIND, val)
+}
+
+#[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+pub(crate) fn get_freebind(socket: TcpSocket) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket, libc::IPPROTO_IP, libc::IP_FREEBIND).map(|val| val != 0)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_bind_address_no_port(socket: TcpSocket, no_port: bool) -> io::Result<()> {
+    let val: libc::c_int = if no_port { 1 } else { 0 };
+    setsockopt(socket, libc::IPPROTO_IP, libc::IP_BIND_ADDRESS_NO_PORT, val)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_bind_address_no_port(socket: TcpSocket) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket, libc::IPPROTO_IP, libc::IP_BIND_ADDRESS_NO_PORT)
+        .map(|val| val != 0)
+}
+
+pub(crate) fn set_keepalive(socket: TcpSocket, keepalive: bool) -> io::Result<()> {
+    let val: libc::c_int = if keepalive { 1 } else { 0 };
+    setsockopt(socket, libc::SOL_SOCKET, libc::SO_KEEPALIVE, val)
+}
+
+pub(crate) fn get_keepalive(socket: TcpSocket) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket, libc::SOL_SOCKET, libc::SO_KEEPALIVE).map(|val| val != 0)
+}
+
+// Apple platforms call `TCP_KEEPIDLE` `TCP_KEEPALIVE`.
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+use libc::TCP_KEEPALIVE as KEEPALIVE_TIME;
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "fuchsia",
+    target_os = "linux",
+    target_os = "netbsd",
+))]
+use libc::TCP_KEEPIDLE as KEEPALIVE_TIME;
+
+cfg_tcp_keepalive! {
+    pub(crate) fn set_keepalive_time(socket: TcpSocket, time: Duration) -> io::Result<()> {
+        setsockopt(socket, libc::IPPROTO_TCP, KEEPALIVE_TIME, secs_round_up(time))
+    }
+
+    pub(crate) fn get_keepalive_time(socket: TcpSocket) -> io::Result<Duration> {
+        getsockopt::<libc::c_int>(socket, libc::IPPROTO_TCP, KEEPALIVE_TIME)
+            .map(|secs| Duration::from_secs(secs as u64))
+    }
+
+    pub(crate) fn set_keepalive_interval(socket: TcpSocket, interval: Duration) -> io::Result<()> {
+        setsockopt(
+            socket,
+            libc::IPPROTO_TCP,
+            libc::TCP_KEEPINTVL,
+            secs_round_up(interval),
+        )
+    }
+
+    pub(crate) fn get_keepalive_interval(socket: TcpSocket) -> io::Result<Duration> {
+        getsockopt::<libc::c_int>(socket, libc::IPPROTO_TCP, libc::TCP_KEEPINTVL)
+            .map(|secs| Duration::from_secs(secs as u64))
+    }
+
+    pub(crate) fn set_keepalive_retries(socket: TcpSocket, retries: u32) -> io::Result<()> {
+        let retries = retries.try_into().unwrap_or(libc::c_int::MAX);
+        setsockopt(socket, libc::IPPROTO_TCP, libc::TCP_KEEPCNT, retries)
+    }
+
+    pub(crate) fn get_keepalive_retries(socket: TcpSocket) -> io::Result<u32> {
+        getsockopt::<libc::c_int>(socket, libc::IPPROTO_TCP, libc::TCP_KEEPCNT)
+            .map(|retries| retries as u32)
+    }
+
+    /// Convert `duration` into whole seconds, rounding up so that a non-zero
+    /// duration is never turned into zero (which the OS rejects).
+    fn secs_round_up(duration: Duration) -> libc::c_int {
+        let secs = if duration.subsec_nanos() == 0 {
+            duration.as_secs()
+        } else {
+            duration.as_secs().saturating_add(1)
+        };
+        secs.try_into().unwrap_or(libc::c_int::MAX)
+    }
+}
+
+/// Convert `duration` into whole seconds, rounding down, as used by the
+/// linger and defer accept options.
+fn secs(duration: Duration) -> libc::c_int {
+    duration
+        .as_secs()
+        .try_into()
+        .unwrap_or(libc::c_int::MAX)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_fastopen(socket: TcpSocket, queue_len: u32) -> io::Result<()> {
+    let queue_len = queue_len.try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(socket, libc::IPPROTO_TCP, libc::TCP_FASTOPEN, queue_len)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_fastopen(socket: TcpSocket) -> io::Result<u32> {
+    getsockopt::<libc::c_int>(socket, libc::IPPROTO_TCP, libc::TCP_FASTOPEN)
+        .map(|queue_len| queue_len as u32)
+}
+
+/// `struct tcp_info` as defined in `linux/tcp.h`, up to
+/// `tcpi_delivery_rate`.
+///
+/// The definition in libc follows glibc, which lacks the newer fields.
+#[cfg(target_os = "linux")]
+#[repr(C)]
+#[derive(Copy, Clone)]
+#[allow(dead_code)] // Not all fields are used.
+struct tcp_info {
+    tcpi_state: u8,
+    tcpi_ca_state: u8,
+    tcpi_retransmits: u8,
+    tcpi_probes: u8,
+    tcpi_backoff: u8,
+    tcpi_options: u8,
+    tcpi_snd_rcv_wscale: u8,
+    tcpi_delivery_rate_app_limited: u8,
+    tcpi_rto: u32,
+    tcpi_ato: u32,
+    tcpi_snd_mss: u32,
+    tcpi_rcv_mss: u32,
+    tcpi_unacked: u32,
+    tcpi_sacked: u32,
+    tcpi_lost: u32,
+    tcpi_retrans: u32,
+    tcpi_fackets: u32,
+    tcpi_last_data_sent: u32,
+    tcpi_last_ack_sent: u32,
+    tcpi_last_data_recv: u32,
+    tcpi_last_ack_recv: u32,
+    tcpi_pmtu: u32,
+    tcpi_rcv_ssthresh: u32,
+    tcpi_rtt: u32,
+    tcpi_rttvar: u32,
+    tcpi_snd_ssthresh: u32,
+    tcpi_snd_cwnd: u32,
+    tcpi_advmss: u32,
+    tcpi_reordering: u32,
+    tcpi_rcv_rtt: u32,
+    tcpi_rcv_space: u32,
+    tcpi_total_retrans: u32,
+    tcpi_pacing_rate: u64,
+    tcpi_max_pacing_rate: u64,
+    tcpi_bytes_acked: u64,
+    tcpi_bytes_received: u64,
+    tcpi_segs_out: u32,
+    tcpi_segs_in: u32,
+    tcpi_notsent_bytes: u32,
+    tcpi_min_rtt: u32,
+    tcpi_data_segs_in: u32,
+    tcpi_data_segs_out: u32,
+    tcpi_delivery_rate: u64,
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_tcp_info(socket: TcpSocket) -> io::Result<TcpInfo> {
+    let mut info: MaybeUninit<tcp_info> = MaybeUninit::zeroed();
+    let mut length = size_of::<tcp_info>() as libc::socklen_t;
+    syscall!(getsockopt(
+        socket,
+        libc::IPPROTO_TCP,
+        libc::TCP_INFO,
+        info.as_mut_ptr() as *mut libc::c_void,
+        &mut length,
+    ))?;
+    // Safety: zeroed above, older kernels only fill a prefix of the struct.
+    let info = unsafe { info.assume_init() };
+    // Returns true if the kernel filled in the field ending at `end`.
+    let has = |end: usize| length as usize >= end;
+    // `tcpi_min_rtt` is followed by two `u32`s and `tcpi_delivery_rate`.
+    let min_rtt_end = size_of::<tcp_info>() - 2 * size_of::<u32>() - size_of::<u64>();
+    Ok(TcpInfo {
+        rtt: Duration::from_micros(info.tcpi_rtt as u64),
+        rtt_var: Duration::from_micros(info.tcpi_rttvar as u64),
+        min_rtt: if has(min_rtt_end) {
+            Some(Duration::from_micros(info.tcpi_min_rtt as u64))
+        } else {
+            None
+        },
+        retransmits: info.tcpi_retransmits,
+        total_retransmits: info.tcpi_total_retrans,
+        congestion_window: info.tcpi_snd_cwnd,
+        send_mss: info.tcpi_snd_mss,
+        delivery_rate: if has(size_of::<tcp_info>()) {
+            Some(info.tcpi_delivery_rate)
+        } else {
+            None
+        },
+    })
+}
+
+// Apple platforms use ticks for `SO_LINGER`, `SO_LINGER_SEC` uses seconds.
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+use libc::SO_LINGER_SEC as LINGER;
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+use libc::SO_LINGER as LINGER;
+
+pub(crate) fn set_linger(socket: TcpSocket, linger: Option<Duration>) -> io::Result<()> {
+    let val = libc::linger {
+        l_onoff: if linger.is_some() { 1 } else { 0 },
+        l_linger: linger.map(secs).unwrap_or(0),
+    };
+    setsockopt(socket, libc::SOL_SOCKET, LINGER, val)
+}
+
+pub(crate) fn get_linger(socket: TcpSocket) -> io::Result<Option<Duration>> {
+    getsockopt::<libc::linger>(socket, libc::SOL_SOCKET, LINGER).map(|val| {
+        if val.l_onoff == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(val.l_linger as u64))
+        }
+    })
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_user_timeout(socket: TcpSocket, timeout: Duration) -> io::Result<()> {
+    let timeout: libc::c_uint = timeout
+        .as_millis()
+        .try_into()
+        .unwrap_or(libc::c_uint::MAX);
+    setsockopt(socket, libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT, timeout)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_user_timeout(socket: TcpSocket) -> io::Result<Duration> {
+    getsockopt::<libc::c_uint>(socket, libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT)
+        .map(|millis| Duration::from_millis(millis as u64))
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_cork(socket: TcpSocket, cork: bool) -> io::Result<()> {
+    let val: libc::c_int = if cork { 1 } else { 0 };
+    setsockopt(socket, libc::IPPROTO_TCP, libc::TCP_CORK, val)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_cork(socket: TcpSocket) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket, libc::IPPROTO_TCP, libc::TCP_CORK).map(|val| val != 0)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_quickack(socket: TcpSocket, quickack: bool) -> io::Result<()> {
+    let val: libc::c_int = if quickack { 1 } else { 0 };
+    setsockopt(socket, libc::IPPROTO_TCP, libc::TCP_QUICKACK, val)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_quickack(socket: TcpSocket) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket, libc::IPPROTO_TCP, libc::TCP_QUICKACK).map(|val| val != 0)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_notsent_lowat(socket: TcpSocket, lowat: u32) -> io::Result<()> {
+    let lowat = lowat.try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(socket, libc::IPPROTO_TCP, libc::TCP_NOTSENT_LOWAT, lowat)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_notsent_lowat(socket: TcpSocket) -> io::Result<u32> {
+    getsockopt::<libc::c_int>(socket, libc::IPPROTO_TCP, libc::TCP_NOTSENT_LOWAT)
+        .map(|lowat| lowat as u32)
+}
+
+/// Maximum length of a congestion control algorithm name, including the nul
+/// byte, `TCP_CA_NAME_MAX` in the kernel.
+#[cfg(target_os = "linux")]
+const TCP_CA_NAME_MAX: usize = 16;
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_congestion(socket: TcpSocket, algorithm: &str) -> io::Result<()> {
     syscall!(setsockopt(
-        socket.as_raw_fd(),
-        libc::SOL_SOCKET,
-        libc::SO_REUSEADDR,
-        &val as *const libc::c_int as *const libc::c_void,
-        size_of::<libc::c_int>() as libc::socklen_t,
+        socket,
+        libc::IPPROTO_TCP,
+        libc::TCP_CONGESTION,
+        algorithm.as_ptr() as *const libc::c_void,
+        algorithm.len() as libc::socklen_t,
+    ))
+    .map(|_| ())
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_congestion(socket: TcpSocket) -> io::Result<String> {
+    let mut buf = [0u8; TCP_CA_NAME_MAX];
+    let mut len = buf.len() as libc::socklen_t;
+    syscall!(getsockopt(
+        socket,
+        libc::IPPROTO_TCP,
+        libc::TCP_CONGESTION,
+        buf.as_mut_ptr() as *mut libc::c_void,
+        &mut len,
     ))?;
-    Ok(())
+    let name = &buf[..len as usize];
+    let name = match name.iter().position(|b| *b == 0) {
+        Some(end) => &name[..end],
+        None => name,
+    };
+    String::from_utf8(name.to_vec()).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_defer_accept(socket: TcpSocket, timeout: Duration) -> io::Result<()> {
+    setsockopt(socket, libc::IPPROTO_TCP, libc::TCP_DEFER_ACCEPT, secs(timeout))
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_defer_accept(socket: TcpSocket) -> io::Result<Duration> {
+    getsockopt::<libc::c_int>(socket, libc::IPPROTO_TCP, libc::TCP_DEFER_ACCEPT)
+        .map(|secs| Duration::from_secs(secs as u64))
 }
 
 pub(crate) fn accept(listener: &net::TcpListener) -> io::Result<(net::TcpStream, SocketAddr)> {
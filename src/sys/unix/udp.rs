@@ -1,9 +1,27 @@
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::RecvMeta;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::sys::unix::net::{getsockopt, setsockopt, to_socket_addr, SocketAddrCRepr};
 use crate::sys::unix::net::{new_ip_socket, socket_addr};
+pub(crate) use crate::sys::unix::net::{
+    recv_from_vectored, recv_from_with_flags, recv_vectored, recv_with_flags, send_to_vectored,
+    send_to_with_flags, send_vectored, send_with_flags,
+};
+#[cfg(target_os = "linux")]
+pub(crate) use crate::sys::unix::net::{
+    get_zerocopy, recv_zerocopy_completion, send_zerocopy, set_zerocopy,
+};
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::cmp;
 use std::io;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::io::{IoSlice, IoSliceMut};
 use std::mem;
 use std::net::{self, SocketAddr};
 use std::os::unix::io::{AsRawFd, FromRawFd};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::ptr;
 
 pub fn bind(addr: SocketAddr) -> io::Result<net::UdpSocket> {
     // Gives a warning for non Apple platforms.
@@ -37,3 +55,212 @@ pub(crate) fn only_v6(socket: &net::UdpSocket) -> io::Result<bool> {
 
     Ok(optval != 0)
 }
+
+/// `UDP_SEGMENT` and `UDP_GRO` from `linux/udp.h`, not defined in libc 0.2.121.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+const UDP_SEGMENT: libc::c_int = 103;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+const UDP_GRO: libc::c_int = 104;
+
+/// Size, in `u64`s, of the control message buffer used when receiving,
+/// large enough for an `UDP_GRO` message.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+const CONTROL_LEN: usize = 4;
+
+/// Flags passed to `sendmmsg(2)`, which musl declares as `unsigned int`.
+#[cfg(all(target_os = "linux", target_env = "musl"))]
+const SEND_MANY_FLAGS: libc::c_uint = libc::MSG_NOSIGNAL as libc::c_uint;
+#[cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    not(target_env = "musl")
+))]
+const SEND_MANY_FLAGS: libc::c_int = libc::MSG_NOSIGNAL;
+
+/// Receive datagrams using `recvmmsg(2)`, one into each buffer in `bufs`,
+/// storing the length and sender in `meta`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_many(
+    socket: &net::UdpSocket,
+    bufs: &mut [IoSliceMut<'_>],
+    meta: &mut [RecvMeta],
+) -> io::Result<usize> {
+    let n = cmp::min(
+        cmp::min(bufs.len(), meta.len()),
+        libc::c_uint::MAX as usize,
+    );
+    // Safety: zeroed `sockaddr_storage` and `mmsghdr` are valid.
+    let mut names: Vec<libc::sockaddr_storage> = vec![unsafe { mem::zeroed() }; n];
+    let mut msgs: Vec<libc::mmsghdr> = vec![unsafe { mem::zeroed() }; n];
+    let mut controls = vec![[0u64; CONTROL_LEN]; n];
+    for (((msg, name), control), buf) in msgs
+        .iter_mut()
+        .zip(names.iter_mut())
+        .zip(controls.iter_mut())
+        .zip(bufs.iter_mut())
+    {
+        msg.msg_hdr.msg_name = name as *mut libc::sockaddr_storage as *mut libc::c_void;
+        msg.msg_hdr.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+        msg.msg_hdr.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_hdr.msg_controllen = mem::size_of_val(control) as _;
+        // `IoSliceMut` is guaranteed to be ABI compatible with `iovec`.
+        msg.msg_hdr.msg_iov = buf as *mut IoSliceMut<'_> as *mut libc::iovec;
+        msg.msg_hdr.msg_iovlen = 1;
+    }
+
+    let received = syscall!(recvmmsg(
+        socket.as_raw_fd(),
+        msgs.as_mut_ptr(),
+        n as libc::c_uint,
+        0,
+        ptr::null_mut(),
+    ))? as usize;
+    for ((meta, msg), name) in meta.iter_mut().zip(msgs.iter()).zip(names.iter()).take(received) {
+        meta.len = msg.msg_len as usize;
+        // Safety: `recvmmsg` initialised the address.
+        meta.addr = unsafe { to_socket_addr(name) }?;
+        meta.segment_size = gro_segment_size(&msg.msg_hdr);
+        meta.truncated = msg.msg_hdr.msg_flags & libc::MSG_TRUNC != 0;
+        meta.control_truncated = msg.msg_hdr.msg_flags & libc::MSG_CTRUNC != 0;
+    }
+    Ok(received)
+}
+
+/// Send datagrams using `sendmmsg(2)`, one for each buffer in `bufs`, to the
+/// matching address in `addrs` or the connected peer if `addrs` is empty.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_many(
+    socket: &net::UdpSocket,
+    bufs: &[IoSlice<'_>],
+    addrs: &[SocketAddr],
+) -> io::Result<usize> {
+    if !addrs.is_empty() && addrs.len() != bufs.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "number of addresses doesn't match the number of buffers",
+        ));
+    }
+
+    let n = cmp::min(bufs.len(), libc::c_uint::MAX as usize);
+    let names: Vec<(SocketAddrCRepr, libc::socklen_t)> =
+        addrs.iter().take(n).map(socket_addr).collect();
+    // Safety: a zeroed `mmsghdr` is valid.
+    let mut msgs: Vec<libc::mmsghdr> = vec![unsafe { mem::zeroed() }; n];
+    for (i, (msg, buf)) in msgs.iter_mut().zip(bufs.iter()).enumerate() {
+        if let Some((name, name_len)) = names.get(i) {
+            msg.msg_hdr.msg_name = name.as_ptr() as *mut libc::c_void;
+            msg.msg_hdr.msg_namelen = *name_len;
+        }
+        // `IoSlice` is guaranteed to be ABI compatible with `iovec`.
+        msg.msg_hdr.msg_iov = buf as *const IoSlice<'_> as *mut libc::iovec;
+        msg.msg_hdr.msg_iovlen = 1;
+    }
+
+    syscall!(sendmmsg(
+        socket.as_raw_fd(),
+        msgs.as_mut_ptr(),
+        n as libc::c_uint,
+        SEND_MANY_FLAGS,
+    ))
+    .map(|sent| sent as usize)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_gro(socket: libc::c_int, gro: bool) -> io::Result<()> {
+    let val: libc::c_int = if gro { 1 } else { 0 };
+    setsockopt(socket, libc::IPPROTO_UDP, UDP_GRO, val)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn get_gro(socket: libc::c_int) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket, libc::IPPROTO_UDP, UDP_GRO).map(|val| val != 0)
+}
+
+/// Receive a single datagram, or multiple coalesced datagrams if `UDP_GRO`
+/// is enabled, using `recvmsg(2)`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_with_meta(socket: &net::UdpSocket, buf: &mut [u8]) -> io::Result<RecvMeta> {
+    // Safety: zeroed `sockaddr_storage` and `msghdr` are valid.
+    let mut name: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    let mut control = [0u64; CONTROL_LEN];
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    msg.msg_name = &mut name as *mut libc::sockaddr_storage as *mut libc::c_void;
+    msg.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    msg.msg_controllen = mem::size_of_val(&control) as _;
+
+    let len = syscall!(recvmsg(socket.as_raw_fd(), &mut msg, 0))? as usize;
+    Ok(RecvMeta {
+        len,
+        // Safety: `recvmsg` initialised the address.
+        addr: unsafe { to_socket_addr(&name) }?,
+        segment_size: gro_segment_size(&msg),
+        truncated: msg.msg_flags & libc::MSG_TRUNC != 0,
+        control_truncated: msg.msg_flags & libc::MSG_CTRUNC != 0,
+    })
+}
+
+/// Returns the segment size from the `UDP_GRO` control message in `msg`, if
+/// any.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn gro_segment_size(msg: &libc::msghdr) -> Option<usize> {
+    // Safety: `msg` is initialised by `recvmsg` or `recvmmsg`.
+    let mut cmsg = unsafe { libc::CMSG_FIRSTHDR(msg) };
+    while !cmsg.is_null() {
+        // Safety: `cmsg` points into the control buffer of `msg`.
+        let (level, kind) = unsafe { ((*cmsg).cmsg_level, (*cmsg).cmsg_type) };
+        if level == libc::IPPROTO_UDP && kind == UDP_GRO {
+            // Safety: the data of a `UDP_GRO` message is an `int`, which may
+            // not be aligned.
+            let size = unsafe { ptr::read_unaligned(libc::CMSG_DATA(cmsg) as *const libc::c_int) };
+            return Some(size as usize);
+        }
+        cmsg = unsafe { libc::CMSG_NXTHDR(msg, cmsg) };
+    }
+    None
+}
+
+/// Send `buf` using `sendmsg(2)` with an `UDP_SEGMENT` control message, to
+/// `target` or the connected peer, letting the kernel split it into
+/// datagrams of `segment_size` bytes.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_segmented(
+    socket: &net::UdpSocket,
+    buf: &[u8],
+    target: Option<SocketAddr>,
+    segment_size: u16,
+) -> io::Result<usize> {
+    // Safety: a zeroed `msghdr` is valid.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    let name = target.map(|addr| socket_addr(&addr));
+    if let Some((name, name_len)) = &name {
+        msg.msg_name = name.as_ptr() as *mut libc::c_void;
+        msg.msg_namelen = *name_len;
+    }
+    let mut iov = libc::iovec {
+        iov_base: buf.as_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+
+    let mut control = [0u64; CONTROL_LEN];
+    msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    // Safety: `CMSG_SPACE` only does arithmetic.
+    msg.msg_controllen = unsafe { libc::CMSG_SPACE(mem::size_of::<u16>() as libc::c_uint) } as _;
+    // Safety: the control buffer is large enough for a single `u16` message.
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::IPPROTO_UDP;
+        (*cmsg).cmsg_type = UDP_SEGMENT;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(mem::size_of::<u16>() as libc::c_uint) as _;
+        ptr::write_unaligned(libc::CMSG_DATA(cmsg) as *mut u16, segment_size);
+    }
+
+    syscall!(sendmsg(socket.as_raw_fd(), &msg, libc::MSG_NOSIGNAL)).map(|n| n as usize)
+}
@@ -1,7 +1,11 @@
 use super::{socket_addr, SocketAddr};
 use crate::sys::unix::net::new_socket;
+use crate::sys::unix::net::{recv_msg, send_msg, SEND_FLAGS};
+pub(crate) use crate::sys::unix::net::{
+    recv_vectored, recv_with_flags, send_vectored, send_with_flags,
+};
 
-use std::io;
+use std::io::{self, IoSlice, IoSliceMut};
 use std::os::unix::io::{AsRawFd, FromRawFd};
 use std::os::unix::net;
 use std::path::Path;
@@ -36,6 +40,14 @@ pub(crate) fn peer_addr(socket: &net::UnixDatagram) -> io::Result<SocketAddr> {
 pub(crate) fn recv_from(
     socket: &net::UnixDatagram,
     dst: &mut [u8],
+) -> io::Result<(usize, SocketAddr)> {
+    recv_from_with_flags(socket, dst, 0)
+}
+
+pub(crate) fn recv_from_with_flags(
+    socket: &net::UnixDatagram,
+    dst: &mut [u8],
+    flags: libc::c_int,
 ) -> io::Result<(usize, SocketAddr)> {
     let mut count = 0;
     let socketaddr = SocketAddr::new(|sockaddr, socklen| {
@@ -43,7 +55,7 @@ pub(crate) fn recv_from(
             socket.as_raw_fd(),
             dst.as_mut_ptr() as *mut _,
             dst.len(),
-            0,
+            flags,
             sockaddr,
             socklen,
         ))
@@ -54,3 +66,49 @@ pub(crate) fn recv_from(
     })?;
     Ok((count as usize, socketaddr))
 }
+
+pub(crate) fn send_to_with_flags(
+    socket: &net::UnixDatagram,
+    buf: &[u8],
+    path: &Path,
+    flags: libc::c_int,
+) -> io::Result<usize> {
+    let (sockaddr, socklen) = socket_addr(path)?;
+    let sockaddr = &sockaddr as *const libc::sockaddr_un as *const libc::sockaddr;
+    syscall!(sendto(
+        socket.as_raw_fd(),
+        buf.as_ptr() as *const libc::c_void,
+        buf.len(),
+        flags | SEND_FLAGS,
+        sockaddr,
+        socklen,
+    ))
+    .map(|n| n as usize)
+}
+
+pub(crate) fn send_to_vectored(
+    socket: &net::UnixDatagram,
+    bufs: &[IoSlice<'_>],
+    path: &Path,
+) -> io::Result<usize> {
+    let (sockaddr, socklen) = socket_addr(path)?;
+    let sockaddr = &sockaddr as *const libc::sockaddr_un as *const libc::sockaddr;
+    // Safety: `sockaddr` is `socklen` bytes.
+    unsafe { send_msg(socket.as_raw_fd(), bufs, sockaddr, socklen) }
+}
+
+pub(crate) fn recv_from_vectored(
+    socket: &net::UnixDatagram,
+    bufs: &mut [IoSliceMut<'_>],
+) -> io::Result<(usize, SocketAddr)> {
+    let mut count = 0;
+    let socketaddr = SocketAddr::new(|sockaddr, socklen| {
+        // Safety: `SocketAddr::new` provides a `sockaddr_un` of `socklen`
+        // bytes.
+        unsafe { recv_msg(socket.as_raw_fd(), bufs, sockaddr, socklen) }.map(|c| {
+            count = c;
+            c as libc::c_int
+        })
+    })?;
+    Ok((count, socketaddr))
+}
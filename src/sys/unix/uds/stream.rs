@@ -1,5 +1,11 @@
 use super::{socket_addr, SocketAddr};
 use crate::sys::unix::net::new_socket;
+pub(crate) use crate::sys::unix::net::{recv_with_flags, send_with_flags, write_vectored};
+cfg_send_file! {
+    pub(crate) use crate::sys::unix::net::send_file;
+}
+#[cfg(all(feature = "os-ext", any(target_os = "android", target_os = "linux")))]
+pub(crate) use crate::sys::unix::net::splice;
 
 use std::io;
 use std::os::unix::io::{AsRawFd, FromRawFd};
@@ -20,8 +20,10 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use crate::event::Readiness;
 #[cfg(feature = "net")]
-use crate::{Interest, Token};
+use crate::Interest;
+use crate::Token;
 
 cfg_net! {
     pub(crate) mod tcp {
@@ -110,6 +112,13 @@ impl Selector {
         }
     }
 
+    pub(crate) fn post(&self, _: Token, _: Readiness) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "posting events is not supported on WASI",
+        ))
+    }
+
     pub(crate) fn try_clone(&self) -> io::Result<Selector> {
         Ok(Selector {
             id: self.id,
@@ -253,9 +262,36 @@ pub(crate) type Event = wasi::Event;
 pub(crate) mod event {
     use std::fmt;
 
-    use crate::sys::Event;
+    use crate::event::Readiness;
+    use crate::sys::{Event, Events};
     use crate::Token;
 
+    /// Push an event with `token` and `readiness` onto `events`, used for
+    /// replayed events. As a WASI event has a single type this pushes two
+    /// events for readiness that is both readable and writable.
+    pub(crate) fn push(events: &mut Events, token: Token, readiness: Readiness) {
+        let event = |type_, hangup| Event {
+            userdata: token.0 as wasi::Userdata,
+            error: wasi::ERRNO_SUCCESS,
+            type_,
+            fd_readwrite: wasi::EventFdReadwrite {
+                nbytes: 0,
+                flags: if hangup {
+                    wasi::EVENTRWFLAGS_FD_READWRITE_HANGUP
+                } else {
+                    0
+                },
+            },
+        };
+
+        if readiness.is_readable() || readiness.is_read_closed() {
+            events.push(event(wasi::EVENTTYPE_FD_READ, readiness.is_read_closed()));
+        }
+        if readiness.is_writable() || readiness.is_write_closed() {
+            events.push(event(wasi::EVENTTYPE_FD_WRITE, readiness.is_write_closed()));
+        }
+    }
+
     pub(crate) fn token(event: &Event) -> Token {
         Token(event.userdata as usize)
     }
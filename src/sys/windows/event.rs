@@ -2,6 +2,7 @@ use std::fmt;
 
 use super::afd;
 use super::iocp::CompletionStatus;
+use crate::event::Readiness;
 use crate::Token;
 
 #[derive(Clone)]
@@ -31,6 +32,31 @@ impl Event {
         self.flags |= afd::POLL_SEND;
     }
 
+    /// Best effort conversion of a posted event, such that the `is_*`
+    /// functions return the same readiness.
+    pub(super) fn from_readiness(token: Token, readiness: Readiness) -> Event {
+        let mut event = Event::new(token);
+        if readiness.is_readable() {
+            event.flags |= afd::POLL_RECEIVE;
+        }
+        if readiness.is_writable() {
+            event.flags |= afd::POLL_SEND;
+        }
+        if readiness.is_error() {
+            event.flags |= afd::POLL_CONNECT_FAIL;
+        }
+        if readiness.is_read_closed() {
+            event.flags |= afd::POLL_DISCONNECT;
+        }
+        if readiness.is_write_closed() {
+            event.flags |= afd::POLL_ABORT;
+        }
+        if readiness.is_priority() {
+            event.flags |= afd::POLL_RECEIVE_EXPEDITED;
+        }
+        event
+    }
+
     pub(super) fn from_completion_status(status: &CompletionStatus) -> Event {
         Event {
             flags: status.bytes_transferred(),
@@ -54,6 +80,13 @@ pub(crate) const READ_CLOSED_FLAGS: u32 =
     afd::POLL_DISCONNECT | afd::POLL_ABORT | afd::POLL_CONNECT_FAIL;
 pub(crate) const WRITE_CLOSED_FLAGS: u32 = afd::POLL_ABORT | afd::POLL_CONNECT_FAIL;
 
+/// Push an event with `token` and `readiness` onto `events`, used for replayed
+/// events.
+#[cfg(feature = "record")]
+pub fn push(events: &mut Events, token: Token, readiness: Readiness) {
+    events.events.push(Event::from_readiness(token, readiness));
+}
+
 pub fn is_readable(event: &Event) -> bool {
     event.flags & READABLE_FLAGS != 0
 }
@@ -135,6 +135,12 @@ cfg_io_source! {
             }
         }
 
+        /// Token of the registration, if registered.
+        #[cfg(feature = "record")]
+        pub fn token(&self) -> Option<Token> {
+            self.inner.as_ref().map(|state| state.token)
+        }
+
         pub fn deregister(&mut self) -> io::Result<()> {
             match self.inner.as_mut() {
                 Some(state) => {
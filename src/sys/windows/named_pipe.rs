@@ -597,7 +597,12 @@ impl<'a> Write for &'a NamedPipe {
 }
 
 impl Source for NamedPipe {
-    fn register(&mut self, registry: &Registry, token: Token, _: Interest) -> io::Result<()> {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        _interests: Interest,
+    ) -> io::Result<()> {
         let mut io = self.inner.io.lock().unwrap();
 
         io.check_association(registry, false)?;
@@ -623,10 +628,19 @@ impl Source for NamedPipe {
 
         Inner::post_register(&self.inner, None);
 
+        #[cfg(feature = "record")]
+        registry
+            .recorder()
+            .register(self.as_raw_handle() as usize as u64, token, _interests);
         Ok(())
     }
 
-    fn reregister(&mut self, registry: &Registry, token: Token, _: Interest) -> io::Result<()> {
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        _interests: Interest,
+    ) -> io::Result<()> {
         let mut io = self.inner.io.lock().unwrap();
 
         io.check_association(registry, true)?;
@@ -636,6 +650,10 @@ impl Source for NamedPipe {
 
         Inner::post_register(&self.inner, None);
 
+        #[cfg(feature = "record")]
+        registry
+            .recorder()
+            .reregister(self.as_raw_handle() as usize as u64, token, _interests);
         Ok(())
     }
 
@@ -651,7 +669,13 @@ impl Source for NamedPipe {
             ));
         }
 
+        #[cfg(feature = "record")]
+        let token = io.token;
         io.token = None;
+        #[cfg(feature = "record")]
+        registry
+            .recorder()
+            .deregister(self.as_raw_handle() as usize as u64, token);
         Ok(())
     }
 }
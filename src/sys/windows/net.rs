@@ -4,8 +4,9 @@ use std::net::SocketAddr;
 use std::sync::Once;
 
 use windows_sys::Win32::Networking::WinSock::{
-    ioctlsocket, socket, AF_INET, AF_INET6, FIONBIO, IN6_ADDR, IN6_ADDR_0, INVALID_SOCKET, IN_ADDR,
-    IN_ADDR_0, SOCKADDR, SOCKADDR_IN, SOCKADDR_IN6, SOCKADDR_IN6_0, SOCKET,
+    getsockopt as win_getsockopt, ioctlsocket, setsockopt as win_setsockopt, socket, AF_INET,
+    AF_INET6, FIONBIO, IN6_ADDR, IN6_ADDR_0, INVALID_SOCKET, IN_ADDR, IN_ADDR_0, SOCKADDR,
+    SOCKADDR_IN, SOCKADDR_IN6, SOCKADDR_IN6_0, SOCKET, SOCKET_ERROR,
 };
 
 /// Initialise the network stack for Windows.
@@ -102,3 +103,40 @@ pub(crate) fn socket_addr(addr: &SocketAddr) -> (SocketAddrCRepr, i32) {
         }
     }
 }
+
+/// Set the socket option `name` at `level` to `value`, `T` must be the type
+/// expected by the option.
+pub(crate) fn setsockopt<T>(socket: SOCKET, level: i32, name: i32, value: T) -> io::Result<()> {
+    syscall!(
+        win_setsockopt(
+            socket,
+            level,
+            name,
+            &value as *const T as *const _,
+            mem::size_of::<T>() as i32,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )
+    .map(|_| ())
+}
+
+/// Get the value of socket option `name` at `level`, `T` must be the type
+/// returned by the option.
+pub(crate) fn getsockopt<T: Copy>(socket: SOCKET, level: i32, name: i32) -> io::Result<T> {
+    // Safety: only used with plain old data types, for which zero is valid.
+    let mut value: T = unsafe { mem::zeroed() };
+    let mut len = mem::size_of::<T>() as i32;
+    syscall!(
+        win_getsockopt(
+            socket,
+            level,
+            name,
+            &mut value as *mut T as *mut _,
+            &mut len,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )?;
+    Ok(value)
+}
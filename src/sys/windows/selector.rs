@@ -1,7 +1,9 @@
 use super::afd::{self, Afd, AfdPollInfo};
 use super::io_status_block::IoStatusBlock;
 use super::Event;
+use crate::event::Readiness;
 use crate::sys::Events;
+use crate::Token;
 
 cfg_net! {
     use crate::sys::event::{
@@ -365,6 +367,11 @@ impl Selector {
         self.inner.select(events, timeout)
     }
 
+    pub fn post(&self, token: Token, readiness: Readiness) -> io::Result<()> {
+        let event = Event::from_readiness(token, readiness);
+        self.inner.cp.post(event.to_completion_status())
+    }
+
     #[cfg(debug_assertions)]
     pub fn register_waker(&self) -> bool {
         self.has_waker.swap(true, Ordering::AcqRel)
@@ -382,7 +389,6 @@ impl Selector {
 
 cfg_io_source! {
     use super::InternalState;
-    use crate::Token;
 
     impl Selector {
         pub(super) fn register(
@@ -1,14 +1,21 @@
+use std::convert::TryInto;
 use std::io;
+use std::mem::ManuallyDrop;
 use std::net::{self, SocketAddr};
-use std::os::windows::io::AsRawSocket;
+use std::os::windows::io::FromRawSocket;
+use std::os::windows::raw::SOCKET as StdSocket; // windows-sys uses usize, stdlib uses u32/u64.
+use std::time::Duration;
 
 use windows_sys::Win32::Networking::WinSock::{
-    self, AF_INET, AF_INET6, SOCKET, SOCKET_ERROR, SOCK_STREAM,
+    self, closesocket, AF_INET, AF_INET6, IPPROTO_IPV6, IPV6_V6ONLY, SOCKET, SOCKET_ERROR,
+    SOCK_STREAM, SOL_SOCKET, SO_KEEPALIVE, SO_LINGER, SO_RCVBUF, SO_REUSEADDR, SO_SNDBUF,
 };
 
-use crate::sys::windows::net::{init, new_socket, socket_addr};
+use crate::sys::windows::net::{getsockopt, init, new_socket, setsockopt, socket_addr};
 
-pub(crate) fn new_for_addr(address: SocketAddr) -> io::Result<SOCKET> {
+pub(crate) type TcpSocket = SOCKET;
+
+pub(crate) fn new_for_addr(address: SocketAddr) -> io::Result<TcpSocket> {
     init();
     let domain = match address {
         SocketAddr::V4(_) => AF_INET,
@@ -17,32 +24,24 @@ pub(crate) fn new_for_addr(address: SocketAddr) -> io::Result<SOCKET> {
     new_socket(domain, SOCK_STREAM)
 }
 
-pub(crate) fn bind(socket: &net::TcpListener, addr: SocketAddr) -> io::Result<()> {
+pub(crate) fn bind(socket: TcpSocket, addr: SocketAddr) -> io::Result<()> {
     use WinSock::bind;
 
     let (raw_addr, raw_addr_length) = socket_addr(&addr);
     syscall!(
-        bind(
-            socket.as_raw_socket() as _,
-            raw_addr.as_ptr(),
-            raw_addr_length
-        ),
+        bind(socket, raw_addr.as_ptr(), raw_addr_length),
         PartialEq::eq,
         SOCKET_ERROR
     )?;
     Ok(())
 }
 
-pub(crate) fn connect(socket: &net::TcpStream, addr: SocketAddr) -> io::Result<()> {
+pub(crate) fn connect(socket: TcpSocket, addr: SocketAddr) -> io::Result<()> {
     use WinSock::connect;
 
     let (raw_addr, raw_addr_length) = socket_addr(&addr);
     let res = syscall!(
-        connect(
-            socket.as_raw_socket() as _,
-            raw_addr.as_ptr(),
-            raw_addr_length
-        ),
+        connect(socket, raw_addr.as_ptr(), raw_addr_length),
         PartialEq::eq,
         SOCKET_ERROR
     );
@@ -53,19 +52,90 @@ pub(crate) fn connect(socket: &net::TcpStream, addr: SocketAddr) -> io::Result<(
     }
 }
 
-pub(crate) fn listen(socket: &net::TcpListener, backlog: u32) -> io::Result<()> {
-    use std::convert::TryInto;
+pub(crate) fn listen(socket: TcpSocket, backlog: u32) -> io::Result<()> {
     use WinSock::listen;
 
     let backlog = backlog.try_into().unwrap_or(i32::max_value());
-    syscall!(
-        listen(socket.as_raw_socket() as _, backlog),
-        PartialEq::eq,
-        SOCKET_ERROR
-    )?;
+    syscall!(listen(socket, backlog), PartialEq::eq, SOCKET_ERROR)?;
     Ok(())
 }
 
+pub(crate) fn close(socket: TcpSocket) {
+    let _ = unsafe { closesocket(socket) };
+}
+
+pub(crate) fn local_addr(socket: TcpSocket) -> io::Result<SocketAddr> {
+    // The standard library doesn't close the socket because of the
+    // `ManuallyDrop`, `socket` stays owned by the caller.
+    let stream = ManuallyDrop::new(unsafe { net::TcpStream::from_raw_socket(socket as StdSocket) });
+    stream.local_addr()
+}
+
+pub(crate) fn set_reuseaddr(socket: TcpSocket, reuseaddr: bool) -> io::Result<()> {
+    let val: i32 = if reuseaddr { 1 } else { 0 };
+    setsockopt(socket, SOL_SOCKET as i32, SO_REUSEADDR as i32, val)
+}
+
+pub(crate) fn get_reuseaddr(socket: TcpSocket) -> io::Result<bool> {
+    getsockopt::<i32>(socket, SOL_SOCKET as i32, SO_REUSEADDR as i32).map(|val| val != 0)
+}
+
+pub(crate) fn set_send_buffer_size(socket: TcpSocket, size: u32) -> io::Result<()> {
+    let size = size.try_into().unwrap_or(i32::MAX);
+    setsockopt(socket, SOL_SOCKET as i32, SO_SNDBUF as i32, size)
+}
+
+pub(crate) fn get_send_buffer_size(socket: TcpSocket) -> io::Result<u32> {
+    getsockopt::<i32>(socket, SOL_SOCKET as i32, SO_SNDBUF as i32).map(|size| size as u32)
+}
+
+pub(crate) fn set_recv_buffer_size(socket: TcpSocket, size: u32) -> io::Result<()> {
+    let size = size.try_into().unwrap_or(i32::MAX);
+    setsockopt(socket, SOL_SOCKET as i32, SO_RCVBUF as i32, size)
+}
+
+pub(crate) fn get_recv_buffer_size(socket: TcpSocket) -> io::Result<u32> {
+    getsockopt::<i32>(socket, SOL_SOCKET as i32, SO_RCVBUF as i32).map(|size| size as u32)
+}
+
+pub(crate) fn set_only_v6(socket: TcpSocket, only_v6: bool) -> io::Result<()> {
+    let val: i32 = if only_v6 { 1 } else { 0 };
+    setsockopt(socket, IPPROTO_IPV6 as i32, IPV6_V6ONLY as i32, val)
+}
+
+pub(crate) fn get_only_v6(socket: TcpSocket) -> io::Result<bool> {
+    getsockopt::<i32>(socket, IPPROTO_IPV6 as i32, IPV6_V6ONLY as i32).map(|val| val != 0)
+}
+
+pub(crate) fn set_keepalive(socket: TcpSocket, keepalive: bool) -> io::Result<()> {
+    let val: i32 = if keepalive { 1 } else { 0 };
+    setsockopt(socket, SOL_SOCKET as i32, SO_KEEPALIVE as i32, val)
+}
+
+pub(crate) fn get_keepalive(socket: TcpSocket) -> io::Result<bool> {
+    getsockopt::<i32>(socket, SOL_SOCKET as i32, SO_KEEPALIVE as i32).map(|val| val != 0)
+}
+
+pub(crate) fn set_linger(socket: TcpSocket, linger: Option<Duration>) -> io::Result<()> {
+    let val = WinSock::linger {
+        l_onoff: if linger.is_some() { 1 } else { 0 },
+        l_linger: linger
+            .map(|linger| linger.as_secs().try_into().unwrap_or(u16::MAX))
+            .unwrap_or(0),
+    };
+    setsockopt(socket, SOL_SOCKET as i32, SO_LINGER as i32, val)
+}
+
+pub(crate) fn get_linger(socket: TcpSocket) -> io::Result<Option<Duration>> {
+    getsockopt::<WinSock::linger>(socket, SOL_SOCKET as i32, SO_LINGER as i32).map(|val| {
+        if val.l_onoff == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(val.l_linger as u64))
+        }
+    })
+}
+
 pub(crate) fn accept(listener: &net::TcpListener) -> io::Result<(net::TcpStream, SocketAddr)> {
     // The non-blocking state of `listener` is inherited. See
     // https://docs.microsoft.com/en-us/windows/win32/api/winsock2/nf-winsock2-accept#remarks.
@@ -1,3 +1,4 @@
+use crate::poll::check_token;
 use crate::{sys, Registry, Token};
 
 use std::io;
@@ -21,6 +22,9 @@ use std::io;
 /// happens if multiple `Waker`s are registered with the same `Poll` is
 /// unspecified.
 ///
+/// On Unix `Token(usize::MAX)` is reserved, creating a `Waker` with it returns
+/// an error.
+///
 /// # Implementation notes
 ///
 /// On platforms that support kqueue this will use the `EVFILT_USER` event
@@ -82,6 +86,7 @@ pub struct Waker {
 impl Waker {
     /// Create a new `Waker`.
     pub fn new(registry: &Registry, token: Token) -> io::Result<Waker> {
+        check_token(token)?;
         #[cfg(debug_assertions)]
         registry.register_waker();
         sys::Waker::new(registry.selector(), token).map(|inner| Waker { inner })
@@ -3,11 +3,12 @@
 
 use std::time::Duration;
 
+use mio::event::{OwnedEvent, Readiness};
 use mio::net::TcpStream;
 use mio::{event, Token, Waker};
 
 mod util;
-use util::init_with_poll;
+use util::{assert_send, assert_sync, init_with_poll};
 
 const WAKE_TOKEN: Token = Token(10);
 
@@ -41,3 +42,43 @@ fn events_all() {
     events.clear();
     assert!(events.is_empty());
 }
+
+#[test]
+fn events_drain() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let waker = Waker::new(poll.registry(), WAKE_TOKEN).unwrap();
+
+    waker.wake().expect("unable to wake");
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+
+    let expected: Vec<OwnedEvent> = events.iter().map(OwnedEvent::from).collect();
+    assert!(!expected.is_empty());
+
+    let drained: Vec<OwnedEvent> = events.drain().collect();
+    assert!(events.is_empty());
+    assert_eq!(drained, expected);
+
+    for event in drained {
+        assert_eq!(event.token(), WAKE_TOKEN);
+        assert!(event.readiness().is_readable());
+        assert!(!event.readiness().is_writable());
+    }
+}
+
+#[test]
+fn owned_event() {
+    assert_send::<OwnedEvent>();
+    assert_sync::<OwnedEvent>();
+
+    let readiness = Readiness::READABLE | Readiness::READ_CLOSED;
+    let event = OwnedEvent::new(Token(1), readiness);
+    assert_eq!(event.token(), Token(1));
+    assert_eq!(event.readiness(), readiness);
+    assert_ne!(event, OwnedEvent::new(Token(1), Readiness::READABLE));
+
+    assert!(Readiness::EMPTY.is_empty());
+    assert_eq!(format!("{:?}", Readiness::EMPTY), "(empty)");
+    assert_eq!(format!("{:?}", readiness), "READABLE | READ_CLOSED");
+}
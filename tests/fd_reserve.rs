@@ -0,0 +1,70 @@
+#![cfg(all(unix, feature = "os-poll", feature = "net"))]
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::net;
+use std::os::unix::net as unix_net;
+
+use mio::net::{FdReserve, TcpListener, UnixListener};
+
+mod util;
+use util::{any_local_address, assert_would_block, init, temp_file};
+
+// This test lowers the file descriptor limit of the process, so it's the only
+// test in this file.
+#[test]
+fn accept_or_shed() {
+    init();
+
+    let tcp_listener = TcpListener::bind(any_local_address()).unwrap();
+    let path = temp_file("fd_reserve_accept_or_shed");
+    let unix_listener = UnixListener::bind(&path).unwrap();
+
+    let mut reserve = FdReserve::new().unwrap();
+    reserve.set_reset(true);
+
+    let mut tcp_client = net::TcpStream::connect(tcp_listener.local_addr().unwrap()).unwrap();
+    let mut unix_client = unix_net::UnixStream::connect(&path).unwrap();
+
+    // Use up all file descriptors.
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    assert_eq!(
+        unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) },
+        0
+    );
+    let original_limit = limit.rlim_cur;
+    limit.rlim_cur = 256;
+    assert_eq!(unsafe { libc::setrlimit(libc::RLIMIT_NOFILE, &limit) }, 0);
+    let mut files = Vec::new();
+    loop {
+        match File::open("/dev/null") {
+            Ok(file) => files.push(file),
+            Err(ref err) if FdReserve::is_exhausted(err) => break,
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+
+    let err = tcp_listener.accept().unwrap_err();
+    assert!(FdReserve::is_exhausted(&err), "unexpected error: {}", err);
+    assert!(tcp_listener.accept_or_shed(&mut reserve).unwrap().is_none());
+    assert_would_block(tcp_listener.accept_or_shed(&mut reserve));
+
+    assert!(unix_listener
+        .accept_or_shed(&mut reserve)
+        .unwrap()
+        .is_none());
+    assert_would_block(unix_listener.accept_or_shed(&mut reserve));
+
+    drop(files);
+    limit.rlim_cur = original_limit;
+    assert_eq!(unsafe { libc::setrlimit(libc::RLIMIT_NOFILE, &limit) }, 0);
+
+    // The connections should be reset and closed, respectively.
+    let mut buf = [0; 8];
+    let err = tcp_client.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+    assert_eq!(unix_client.read(&mut buf).unwrap(), 0);
+}
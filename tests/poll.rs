@@ -4,10 +4,10 @@
 use std::net;
 use std::sync::{Arc, Barrier};
 use std::thread::{self, sleep};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{fmt, io};
 
-use mio::event::Source;
+use mio::event::{OwnedEvent, Readiness, Source};
 use mio::net::{TcpListener, TcpStream, UdpSocket};
 use mio::{event, Events, Interest, Poll, Registry, Token};
 
@@ -96,6 +96,253 @@ fn zero_duration_polls_events() {
     drop(listener);
 }
 
+#[test]
+fn poll_until_deadline() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+
+    let deadline = Instant::now() + Duration::from_millis(50);
+    poll.poll_until(&mut events, deadline).unwrap();
+    assert!(events.is_empty());
+    assert!(Instant::now() >= deadline);
+
+    // A deadline in the past should still poll once.
+    poll.poll_until(&mut events, Instant::now() - Duration::from_millis(10))
+        .unwrap();
+    assert!(events.is_empty());
+}
+
+#[test]
+fn poll_until_zero_capacity() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(0);
+
+    let start = Instant::now();
+    let err = poll
+        .poll_until(&mut events, start + Duration::from_secs(10))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn poll_until_returns_events() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    let start = Instant::now();
+    poll.poll_until(&mut events, start + Duration::from_secs(10))
+        .unwrap();
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert!(events.iter().any(|event| event.token() == ID1));
+}
+
+#[test]
+#[cfg(unix)]
+fn poll_retry_interrupted() {
+    init();
+
+    extern "C" fn noop_handler(_: libc::c_int) {}
+
+    // Install a signal handler without `SA_RESTART`, so that `poll` gets
+    // interrupted.
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = noop_handler as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        assert_eq!(
+            libc::sigaction(libc::SIGUSR2, &action, std::ptr::null_mut()),
+            0
+        );
+    }
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+    assert!(!poll.retry_interrupted());
+
+    let interrupt = |delay| {
+        let target = unsafe { libc::pthread_self() } as usize;
+        thread::spawn(move || {
+            sleep(delay);
+            unsafe { libc::pthread_kill(target as libc::pthread_t, libc::SIGUSR2) };
+        })
+    };
+
+    let handle = interrupt(Duration::from_millis(20));
+    let err = poll
+        .poll(&mut events, Some(Duration::from_secs(10)))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+    handle.join().unwrap();
+
+    poll.set_retry_interrupted(true);
+    assert!(poll.retry_interrupted());
+    let handle = interrupt(Duration::from_millis(20));
+    let start = Instant::now();
+    let timeout = Duration::from_millis(200);
+    poll.poll(&mut events, Some(timeout)).unwrap();
+    assert!(start.elapsed() >= timeout);
+    assert!(events.is_empty());
+    handle.join().unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn poll_after_fork() {
+    use mio::net::UnixStream;
+    use std::io::Write;
+
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+
+    // Requires tracking registrations.
+    assert!(poll.after_fork().is_err());
+    poll.track_registrations();
+
+    let (mut receiver, mut sender) = UnixStream::pair().unwrap();
+    poll.registry()
+        .register(&mut receiver, ID1, Interest::READABLE)
+        .unwrap();
+
+    let pid = unsafe { libc::fork() };
+    assert!(pid >= 0, "fork failed: {}", io::Error::last_os_error());
+    if pid == 0 {
+        // Child process, don't panic here.
+        let res = poll.after_fork().and_then(|()| {
+            sender.write_all(b"hello")?;
+            poll.poll(&mut events, Some(Duration::from_secs(5)))?;
+            // Should only affect the child's `Poll`.
+            poll.registry().deregister(&mut receiver)?;
+            Ok(events.iter().any(|event| event.token() == ID1))
+        });
+        let code = if let Ok(true) = res { 0 } else { 1 };
+        unsafe { libc::_exit(code) };
+    }
+
+    let mut status = 0;
+    assert_eq!(unsafe { libc::waitpid(pid, &mut status, 0) }, pid);
+    assert!(libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0);
+
+    // The parent's `Poll` should be unaffected.
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+}
+
+#[test]
+fn post_events() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(2);
+
+    poll.registry().post(ID1, Readiness::READABLE).unwrap();
+    poll.registry()
+        .post(ID2, Readiness::WRITABLE | Readiness::ERROR)
+        .unwrap();
+    // Doesn't fit in `events`, should be returned by the next poll.
+    poll.registry().post(ID3, Readiness::READABLE).unwrap();
+
+    // Shouldn't block.
+    let start = Instant::now();
+    poll.poll(&mut events, None).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(1));
+    let got: Vec<_> = events.iter().map(OwnedEvent::from).collect();
+    assert_eq!(got.len(), 2);
+    assert_eq!(got[0].token(), ID1);
+    assert!(got[0].readiness().is_readable());
+    assert!(!got[0].readiness().is_writable());
+    assert_eq!(got[1].token(), ID2);
+    assert!(got[1].readiness().is_writable());
+    assert!(got[1].readiness().is_error());
+
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    let got: Vec<_> = events.iter().map(OwnedEvent::from).collect();
+    assert_eq!(got.len(), 1);
+    assert_eq!(got[0].token(), ID3);
+
+    // Posted events are returned alongside kernel events.
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID2, Interest::WRITABLE)
+        .unwrap();
+    poll.registry().post(ID1, Readiness::READABLE).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![
+            ExpectEvent::new(ID1, Interest::READABLE),
+            ExpectEvent::new(ID2, Interest::WRITABLE),
+        ],
+    );
+
+    // Posting from a cloned `Registry`.
+    let registry = poll.registry().try_clone().unwrap();
+    registry.post(ID3, Readiness::READABLE).unwrap();
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    assert!(events.iter().any(|event| event.token() == ID3));
+}
+
+#[test]
+fn post_wakes_poll() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(1);
+
+    let registry = poll.registry().try_clone().unwrap();
+    let handle = thread::spawn(move || {
+        sleep(Duration::from_millis(50));
+        registry.post(ID1, Readiness::READABLE).unwrap();
+        registry
+            .post(ID2, Readiness::WRITABLE | Readiness::READ_CLOSED)
+            .unwrap();
+    });
+
+    // Posting should wake up the blocked poll, without returning the waker's
+    // own events.
+    let start = Instant::now();
+    let mut got = Vec::new();
+    while got.len() < 2 {
+        poll.poll(&mut events, Some(Duration::from_secs(10)))
+            .unwrap();
+        // Never more events than the capacity.
+        assert!(events.iter().count() <= 1);
+        got.extend(events.iter().map(OwnedEvent::from));
+    }
+    assert!(start.elapsed() < Duration::from_secs(10));
+    handle.join().unwrap();
+
+    assert_eq!(got[0].token(), ID1);
+    assert!(got[0].readiness().is_readable());
+    assert_eq!(got[1].token(), ID2);
+    assert!(got[1].readiness().is_writable());
+    assert!(got[1].readiness().is_read_closed());
+
+    // No more events.
+    poll.poll(&mut events, Some(Duration::from_millis(10)))
+        .unwrap();
+    assert!(events.is_empty());
+}
+
 #[test]
 fn poll_closes_fd() {
     init();
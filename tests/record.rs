@@ -0,0 +1,272 @@
+#![cfg(not(target_os = "wasi"))]
+#![cfg(all(feature = "os-poll", feature = "net", feature = "record"))]
+
+use std::io::{self, Cursor, Write};
+use std::net;
+#[cfg(unix)]
+use std::os::unix::io::AsRawFd;
+#[cfg(windows)]
+use std::os::windows::io::AsRawSocket;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use mio::event::{OwnedEvent, Readiness};
+use mio::net::TcpStream;
+use mio::record::{Reader, Record};
+use mio::{Events, Interest, Poll, Token};
+
+mod util;
+use util::{any_local_address, init};
+
+const ID1: Token = Token(1);
+const ID2: Token = Token(2);
+
+/// In memory log that can be shared with `Poll`.
+#[derive(Clone, Default)]
+struct SharedLog(Arc<Mutex<Vec<u8>>>);
+
+impl SharedLog {
+    fn bytes(&self) -> Vec<u8> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl Write for SharedLog {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The file descriptor (or socket) as recorded in the log.
+#[cfg(unix)]
+fn raw_fd(stream: &TcpStream) -> u64 {
+    stream.as_raw_fd() as u64
+}
+
+#[cfg(windows)]
+fn raw_fd(stream: &TcpStream) -> u64 {
+    stream.as_raw_socket()
+}
+
+#[test]
+fn record_and_replay() {
+    init();
+
+    let log = SharedLog::default();
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+    poll.start_recording(log.clone()).unwrap();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    assert!(events
+        .iter()
+        .any(|event| event.token() == ID1 && event.is_writable()));
+    let recorded: Vec<OwnedEvent> = events.iter().map(OwnedEvent::from).collect();
+
+    poll.registry()
+        .reregister(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry().post(ID2, Readiness::READABLE).unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(10)))
+        .unwrap();
+    poll.registry().pause(&mut stream).unwrap();
+    poll.registry().resume(&mut stream).unwrap();
+    poll.registry().deregister(&mut stream).unwrap();
+    poll.stop_recording().unwrap();
+
+    // Not recorded.
+    poll.poll(&mut events, Some(Duration::from_millis(10)))
+        .unwrap();
+
+    let records = Reader::new(Cursor::new(log.bytes()))
+        .unwrap()
+        .collect::<io::Result<Vec<Record>>>()
+        .unwrap();
+    let stream_fd = raw_fd(&stream);
+    assert_eq!(records.len(), 7);
+    match &records[0] {
+        Record::Register {
+            fd,
+            token,
+            interests,
+            ..
+        } => {
+            assert_eq!(*fd, stream_fd);
+            assert_eq!(*token, ID1);
+            assert_eq!(*interests, Interest::WRITABLE);
+        }
+        record => panic!("unexpected record: {:?}", record),
+    }
+    match &records[1] {
+        Record::Poll {
+            events, timeout, ..
+        } => {
+            assert_eq!(*events, recorded);
+            assert!(timeout.is_some());
+        }
+        record => panic!("unexpected record: {:?}", record),
+    }
+    match &records[2] {
+        Record::Reregister {
+            fd,
+            token,
+            interests,
+            ..
+        } => {
+            assert_eq!(*fd, stream_fd);
+            assert_eq!(*token, ID1);
+            assert_eq!(*interests, Interest::READABLE);
+        }
+        record => panic!("unexpected record: {:?}", record),
+    }
+    match &records[3] {
+        Record::Poll {
+            events, timeout, ..
+        } => {
+            assert_eq!(*events, vec![OwnedEvent::new(ID2, Readiness::READABLE)]);
+            assert_eq!(*timeout, Some(Duration::from_millis(10)));
+        }
+        record => panic!("unexpected record: {:?}", record),
+    }
+    match &records[4] {
+        Record::Pause { fd, token, .. } => {
+            assert_eq!(*fd, stream_fd);
+            assert_eq!(*token, ID1);
+        }
+        record => panic!("unexpected record: {:?}", record),
+    }
+    match &records[5] {
+        Record::Resume { fd, token, .. } => {
+            assert_eq!(*fd, stream_fd);
+            assert_eq!(*token, ID1);
+        }
+        record => panic!("unexpected record: {:?}", record),
+    }
+    match &records[6] {
+        Record::Deregister { fd, token, .. } => {
+            assert_eq!(*fd, stream_fd);
+            assert_eq!(*token, Some(ID1));
+        }
+        record => panic!("unexpected record: {:?}", record),
+    }
+
+    // The replay doesn't have any registrations, but returns the recorded
+    // events.
+    let mut replay = Poll::replay(Cursor::new(log.bytes())).unwrap();
+    replay.poll(&mut events, None).unwrap();
+    let got: Vec<OwnedEvent> = events.iter().map(OwnedEvent::from).collect();
+    assert_eq!(got, recorded);
+
+    // Events that don't fit are returned by the next call.
+    let mut small_events = Events::with_capacity(0);
+    replay.poll(&mut small_events, None).unwrap();
+    assert!(small_events.is_empty());
+    replay.poll(&mut events, None).unwrap();
+    let got: Vec<OwnedEvent> = events.iter().map(OwnedEvent::from).collect();
+    assert_eq!(got, vec![OwnedEvent::new(ID2, Readiness::READABLE)]);
+
+    let err = replay.poll(&mut events, None).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn replay_poll_until() {
+    init();
+
+    let log = SharedLog::default();
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+    poll.start_recording(log.clone()).unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(0)))
+        .unwrap();
+    assert!(events.is_empty());
+    poll.stop_recording().unwrap();
+
+    // A recorded poll call without events is returned as is, not retried
+    // until the deadline.
+    let mut replay = Poll::replay(Cursor::new(log.bytes())).unwrap();
+    let start = Instant::now();
+    replay
+        .poll_until(&mut events, start + Duration::from_secs(10))
+        .unwrap();
+    assert!(events.is_empty());
+    assert!(start.elapsed() < Duration::from_secs(10));
+
+    let err = replay
+        .poll_until(&mut events, Instant::now() + Duration::from_secs(10))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+/// Log that accepts the header, but fails all other writes.
+struct BrokenLog {
+    header_written: bool,
+}
+
+impl Write for BrokenLog {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.header_written {
+            Err(io::ErrorKind::WriteZero.into())
+        } else {
+            self.header_written = true;
+            Ok(buf.len())
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn record_write_error() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+    let log = BrokenLog {
+        header_written: false,
+    };
+    poll.start_recording(log).unwrap();
+
+    // Failing to write the records doesn't change the results of the calls.
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    poll.registry().post(ID2, Readiness::READABLE).unwrap();
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    assert!(events.iter().any(|event| event.token() == ID2));
+    poll.registry().deregister(&mut stream).unwrap();
+
+    // The recording is stopped after the first error.
+    poll.stop_recording().unwrap();
+}
+
+#[test]
+fn replay_invalid_log() {
+    let err = Poll::replay(Cursor::new(b"not a mio log".to_vec())).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let err = Reader::new(Cursor::new(Vec::new())).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+    // Truncated record.
+    let mut log = b"mio-log\x01".to_vec();
+    log.push(0);
+    let mut reader = Reader::new(Cursor::new(log)).unwrap();
+    let err = reader.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -244,3 +244,173 @@ fn registering_after_deregistering() {
         .unwrap();
     assert!(events.is_empty());
 }
+
+#[test]
+#[cfg(unix)]
+fn register_reserved_token() {
+    init();
+
+    let poll = Poll::new().unwrap();
+    let mut server = TcpListener::bind(any_local_address()).unwrap();
+
+    let err = poll
+        .registry()
+        .register(&mut server, Token(usize::MAX), Interest::READABLE)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    poll.registry()
+        .register(&mut server, SERVER, Interest::READABLE)
+        .unwrap();
+    let err = poll
+        .registry()
+        .reregister(&mut server, Token(usize::MAX), Interest::READABLE)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn transfer_between_event_loops() {
+    init();
+
+    let mut poll1 = Poll::new().unwrap();
+    let mut poll2 = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let listener = std::net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll1
+        .registry()
+        .register(&mut stream, SERVER, Interest::READABLE)
+        .unwrap();
+
+    // Make the stream readable before `poll1` gets a chance to see it.
+    peer.write_all(b"hello").unwrap();
+    sleep(Duration::from_millis(10));
+
+    poll1
+        .registry()
+        .transfer(&mut stream, poll2.registry(), CLIENT, Interest::READABLE)
+        .unwrap();
+
+    // The readiness should not be lost in the move.
+    poll2
+        .poll(&mut events, Some(Duration::from_millis(500)))
+        .unwrap();
+    assert!(events
+        .iter()
+        .any(|event| event.token() == CLIENT && event.is_readable()));
+
+    poll1
+        .poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert!(events.is_empty());
+
+    // The stream should now be associated with `poll2`.
+    poll2
+        .registry()
+        .reregister(&mut stream, CLIENT, Interest::READABLE)
+        .unwrap();
+    #[cfg(debug_assertions)]
+    assert_error(
+        poll1
+            .registry()
+            .reregister(&mut stream, SERVER, Interest::READABLE),
+        "I/O source already registered with a different `Registry`",
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn pause_and_resume() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let listener = std::net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register(&mut stream, CLIENT, Interest::READABLE)
+        .unwrap();
+    poll.registry().pause(&mut stream).unwrap();
+
+    peer.write_all(b"hello").unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert!(events.is_empty());
+
+    // The readiness gained while paused should be returned after resuming.
+    poll.registry().resume(&mut stream).unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(500)))
+        .unwrap();
+    assert!(events
+        .iter()
+        .any(|event| event.token() == CLIENT && event.is_readable()));
+
+    // Re-registering also resumes.
+    poll.registry().pause(&mut stream).unwrap();
+    peer.write_all(b"world").unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert!(events.is_empty());
+    poll.registry()
+        .reregister(&mut stream, SERVER, Interest::READABLE)
+        .unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(500)))
+        .unwrap();
+    assert!(events
+        .iter()
+        .any(|event| event.token() == SERVER && event.is_readable()));
+
+    // Can't resume a deregistered source.
+    poll.registry().deregister(&mut stream).unwrap();
+    assert!(poll.registry().resume(&mut stream).is_err());
+}
+
+#[test]
+#[cfg(all(unix, feature = "os-ext"))]
+fn pause_and_resume_source_fd() {
+    use mio::unix::SourceFd;
+    use std::os::unix::io::AsRawFd;
+
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let listener = std::net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    stream.set_nonblocking(true).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+    let fd = stream.as_raw_fd();
+
+    // `SourceFd` requires registrations to be tracked.
+    poll.registry()
+        .register(&mut SourceFd(&fd), CLIENT, Interest::READABLE)
+        .unwrap();
+    assert!(poll.registry().pause(&mut SourceFd(&fd)).is_err());
+    poll.registry().deregister(&mut SourceFd(&fd)).unwrap();
+
+    poll.track_registrations();
+    poll.registry()
+        .register(&mut SourceFd(&fd), CLIENT, Interest::READABLE)
+        .unwrap();
+    poll.registry().pause(&mut SourceFd(&fd)).unwrap();
+
+    peer.write_all(b"hello").unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert!(events.is_empty());
+
+    poll.registry().resume(&mut SourceFd(&fd)).unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(500)))
+        .unwrap();
+    assert!(events
+        .iter()
+        .any(|event| event.token() == CLIENT && event.is_readable()));
+}
@@ -0,0 +1,100 @@
+#![cfg(any(target_os = "android", target_os = "linux"))]
+#![cfg(all(feature = "os-poll", feature = "net"))]
+
+use std::io::{self, IoSlice, Write};
+use std::net::{self, Shutdown};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread::sleep;
+use std::time::Duration;
+
+use mio::net::{MsgFlags, TcpStream, UnixDatagram, UnixStream};
+
+mod util;
+use util::{any_local_address, init, temp_file};
+
+const DATA: &[u8] = b"Hello world!";
+
+static SIGPIPE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+extern "C" fn count_sigpipe(_: libc::c_int) {
+    SIGPIPE_COUNT.fetch_add(1, Ordering::SeqCst);
+}
+
+// The Rust runtime ignores `SIGPIPE`, which hides the signal. This test
+// installs a handler to count the signals instead, so it's the only test in
+// this file.
+#[test]
+fn no_sigpipe() {
+    init();
+
+    // Write to a closed pipe while `SIGPIPE` is still ignored, the handler
+    // installed below must still be respected afterwards.
+    #[cfg(feature = "os-ext")]
+    {
+        let (mut sender, receiver) = mio::unix::pipe::new().unwrap();
+        drop(receiver);
+        let err = sender.write(DATA).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    }
+
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = count_sigpipe as *const () as libc::sighandler_t;
+        assert_eq!(
+            libc::sigaction(libc::SIGPIPE, &action, std::ptr::null_mut()),
+            0
+        );
+    }
+
+    // TCP, `write` already uses `MSG_NOSIGNAL` in std, `write_vectored`
+    // doesn't.
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (peer, _) = listener.accept().unwrap();
+    drop(peer);
+    let err = loop {
+        match (&stream).write_vectored(&[IoSlice::new(DATA)]) {
+            Ok(_) => sleep(Duration::from_millis(10)),
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                sleep(Duration::from_millis(10))
+            }
+            Err(err) => break err,
+        }
+    };
+    assert!(
+        matches!(
+            err.kind(),
+            io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset
+        ),
+        "unexpected error: {}",
+        err
+    );
+
+    let (stream, peer) = UnixStream::pair().unwrap();
+    drop(peer);
+    let err = (&stream).write(DATA).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    let err = (&stream).write_vectored(&[IoSlice::new(DATA)]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+
+    let path = temp_file("no_sigpipe");
+    let _receiver = UnixDatagram::bind(&path).unwrap();
+    let socket = UnixDatagram::unbound().unwrap();
+    socket.shutdown(Shutdown::Write).unwrap();
+    let err = socket
+        .send_to_with_flags(DATA, &path, MsgFlags::EMPTY)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+
+    #[cfg(feature = "os-ext")]
+    {
+        let (mut sender, receiver) = mio::unix::pipe::new().unwrap();
+        drop(receiver);
+        let err = sender.write(DATA).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+        let err = sender.write_vectored(&[IoSlice::new(DATA)]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    }
+
+    assert_eq!(SIGPIPE_COUNT.load(Ordering::SeqCst), 0);
+}
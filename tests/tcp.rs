@@ -1,7 +1,7 @@
 #![cfg(not(target_os = "wasi"))]
 #![cfg(all(feature = "os-poll", feature = "net"))]
 
-use mio::net::{TcpListener, TcpStream};
+use mio::net::{ConnectStatus, TcpListener, TcpStream};
 use mio::{Events, Interest, Poll, Token};
 use std::io::{self, Read, Write};
 use std::net::{self, Shutdown};
@@ -581,6 +581,86 @@ fn connect_error() {
     assert!(stream.take_error().unwrap().is_some());
 }
 
+#[test]
+#[cfg(all(unix, feature = "os-ext"))]
+fn connect_error_source_fd_take_error() {
+    use mio::unix::SourceFd;
+    use std::os::unix::io::AsRawFd;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    // Pick a "random" port that shouldn't be in use.
+    let mut stream = match TcpStream::connect("127.0.0.1:58382".parse().unwrap()) {
+        Ok(l) => l,
+        Err(ref e) if e.kind() == io::ErrorKind::ConnectionRefused => return,
+        Err(e) => panic!("TcpStream::connect unexpected error {:?}", e),
+    };
+
+    poll.registry()
+        .register(&mut stream, Token(0), Interest::WRITABLE)
+        .unwrap();
+
+    'outer: loop {
+        poll.poll(&mut events, None).unwrap();
+
+        for event in &events {
+            if event.token() == Token(0) && event.is_error() {
+                break 'outer;
+            }
+        }
+    }
+
+    let fd = stream.as_raw_fd();
+    let err = SourceFd(&fd).take_error().unwrap().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+    // Retrieving the error clears it.
+    assert!(SourceFd(&fd).take_error().unwrap().is_none());
+    assert!(stream.take_error().unwrap().is_none());
+}
+
+#[test]
+fn finish_connect() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, Token(0), Interest::WRITABLE)
+        .unwrap();
+
+    loop {
+        match stream.finish_connect().unwrap() {
+            ConnectStatus::Connected => break,
+            ConnectStatus::InProgress => poll.poll(&mut events, None).unwrap(),
+        }
+    }
+    assert_eq!(stream.finish_connect().unwrap(), ConnectStatus::Connected);
+}
+
+#[test]
+fn connect_error_finish_connect() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Pick a "random" port that shouldn't be in use.
+    let mut stream = match TcpStream::connect("127.0.0.1:58383".parse().unwrap()) {
+        Ok(l) => l,
+        Err(ref e) if e.kind() == io::ErrorKind::ConnectionRefused => return,
+        Err(e) => panic!("TcpStream::connect unexpected error {:?}", e),
+    };
+    poll.registry()
+        .register(&mut stream, Token(0), Interest::WRITABLE)
+        .unwrap();
+
+    let err = loop {
+        match stream.finish_connect() {
+            Ok(ConnectStatus::Connected) => panic!("unexpected connection"),
+            Ok(ConnectStatus::InProgress) => poll.poll(&mut events, None).unwrap(),
+            Err(err) => break err,
+        }
+    };
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+}
+
 #[test]
 fn write_error() {
     init();
@@ -0,0 +1,174 @@
+#![cfg(not(target_os = "wasi"))]
+#![cfg(all(feature = "os-poll", feature = "net"))]
+
+use std::io;
+use std::net::{self, SocketAddr};
+use std::time::{Duration, Instant};
+
+#[cfg(target_os = "linux")]
+use mio::net::TcpSocket;
+use mio::net::{TcpConnect, TcpStream};
+use mio::{Events, Interest, Poll, Token};
+
+mod util;
+use util::{any_local_address, assert_send, assert_sync, init_with_poll};
+
+const ID1: Token = Token(0);
+
+#[test]
+fn is_send_and_sync() {
+    assert_send::<TcpConnect>();
+    assert_sync::<TcpConnect>();
+}
+
+/// Returns an address nothing is listening on.
+fn closed_address() -> SocketAddr {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    listener.local_addr().unwrap()
+}
+
+/// Polls until `connect` completes.
+fn wait_for_connect(
+    poll: &mut Poll,
+    events: &mut Events,
+    connect: &mut TcpConnect,
+) -> io::Result<TcpStream> {
+    for _ in 0..100 {
+        poll.poll(
+            events,
+            Some(connect.timeout().unwrap_or(Duration::from_millis(100))),
+        )?;
+        if let Some(stream) = connect.try_connect(poll.registry())? {
+            return Ok(stream);
+        }
+    }
+    panic!("connect didn't complete");
+}
+
+#[test]
+fn connect_single_address() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let mut connect = TcpConnect::new(vec![addr]).unwrap();
+    assert_eq!(connect.timeout(), None);
+    poll.registry()
+        .register(&mut connect, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    let stream = wait_for_connect(&mut poll, &mut events, &mut connect).unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+    let (_, peer_addr) = listener.accept().unwrap();
+    assert_eq!(peer_addr, stream.local_addr().unwrap());
+}
+
+#[test]
+fn connect_fallback_after_failure() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let mut connect = TcpConnect::new(vec![closed_address(), addr]).unwrap();
+    assert!(connect.timeout().unwrap() <= Duration::from_millis(250));
+    poll.registry()
+        .register(&mut connect, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    let stream = wait_for_connect(&mut poll, &mut events, &mut connect).unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+}
+
+#[test]
+fn connect_fallback_without_attempt_delay() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // The next address must be attempted as soon as the first one is refused,
+    // not once the attempt delay expired.
+    let mut connect = TcpConnect::new(vec![closed_address(), addr]).unwrap();
+    connect.set_attempt_delay(Duration::from_secs(10));
+    poll.registry()
+        .register(&mut connect, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    let start = Instant::now();
+    let stream = wait_for_connect(&mut poll, &mut events, &mut connect).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn connect_replaces_failed_attempt_while_others_in_progress() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Connections to a listener with a full backlog stay in progress.
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.bind(any_local_address()).unwrap();
+    let full = socket.listen(0).unwrap();
+    let full_addr = full.local_addr().unwrap();
+    let _queued = net::TcpStream::connect(full_addr).unwrap();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let mut connect = TcpConnect::new(vec![full_addr, closed_address(), addr]).unwrap();
+    poll.registry()
+        .register(&mut connect, ID1, Interest::WRITABLE)
+        .unwrap();
+    // Start the attempt to the closed address right away.
+    connect.set_attempt_delay(Duration::from_millis(0));
+    assert!(connect.try_connect(poll.registry()).unwrap().is_none());
+
+    // Once that attempt is refused the last address is attempted, without
+    // waiting for the attempt delay.
+    connect.set_attempt_delay(Duration::from_secs(10));
+    let start = Instant::now();
+    let stream = wait_for_connect(&mut poll, &mut events, &mut connect).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+}
+
+#[test]
+fn connect_races_attempts() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // Without a delay all attempts are started right away.
+    let mut connect = TcpConnect::new(vec![addr, addr, addr]).unwrap();
+    connect.set_attempt_delay(Duration::from_millis(0));
+    assert_eq!(connect.timeout(), Some(Duration::from_millis(0)));
+    poll.registry()
+        .register(&mut connect, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    let stream = wait_for_connect(&mut poll, &mut events, &mut connect).unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+    assert_eq!(connect.timeout(), None);
+}
+
+#[test]
+fn connect_all_failed() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut connect = TcpConnect::new(vec![closed_address(), closed_address()]).unwrap();
+    poll.registry()
+        .register(&mut connect, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    let err = wait_for_connect(&mut poll, &mut events, &mut connect).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+}
+
+#[test]
+fn connect_no_addresses() {
+    let err = TcpConnect::new(Vec::new()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
@@ -9,6 +9,8 @@ use std::net::{self, SocketAddr};
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
 use std::sync::{Arc, Barrier};
 use std::thread;
+#[cfg(target_os = "linux")]
+use std::time::Duration;
 
 mod util;
 use util::{
@@ -104,6 +106,53 @@ fn set_get_ttl() {
     assert!(listener.take_error().unwrap().is_none());
 }
 
+#[test]
+#[cfg(target_os = "linux")]
+fn set_get_defer_accept() {
+    init();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+
+    assert_eq!(listener.defer_accept().unwrap(), Duration::from_secs(0));
+    // The kernel rounds the timeout to a number of retransmissions.
+    listener.set_defer_accept(Duration::from_secs(5)).unwrap();
+    assert!(listener.defer_accept().unwrap() >= Duration::from_secs(1));
+    listener.set_defer_accept(Duration::from_secs(0)).unwrap();
+    assert_eq!(listener.defer_accept().unwrap(), Duration::from_secs(0));
+    assert!(listener.take_error().unwrap().is_none());
+}
+
+#[test]
+fn accept_many() {
+    init();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let mut accepted = Vec::new();
+    assert_would_block(listener.accept_many(&mut accepted, 2));
+    assert!(accepted.is_empty());
+
+    let streams: Vec<_> = (0..3)
+        .map(|_| net::TcpStream::connect(address).unwrap())
+        .collect();
+
+    // Limited to `max` connections.
+    assert_eq!(listener.accept_many(&mut accepted, 2).unwrap(), 2);
+    assert_eq!(accepted.len(), 2);
+    // Stops at the last pending connection.
+    assert_eq!(listener.accept_many(&mut accepted, 2).unwrap(), 1);
+    assert_eq!(accepted.len(), 3);
+    assert_would_block(listener.accept_many(&mut accepted, 2));
+
+    for (stream, peer_address) in &accepted {
+        assert!(streams
+            .iter()
+            .any(|s| s.local_addr().unwrap() == *peer_address));
+        assert_eq!(stream.local_addr().unwrap(), address);
+    }
+}
+
 #[test]
 fn get_ttl_without_previous_set() {
     init();
@@ -0,0 +1,192 @@
+#![cfg(not(target_os = "wasi"))]
+#![cfg(all(feature = "os-poll", feature = "net"))]
+
+use std::io;
+#[cfg(target_os = "linux")]
+use std::io::{Read, Write};
+use std::net::SocketAddr;
+
+use mio::net::TcpSocket;
+#[cfg(target_os = "linux")]
+use mio::net::{TcpListener, TcpStream};
+use mio::{Interest, Token};
+
+mod util;
+use util::{
+    any_local_address, assert_send, assert_sync, expect_events, init_with_poll, ExpectEvent,
+};
+
+const ID1: Token = Token(0);
+const ID2: Token = Token(1);
+#[cfg(target_os = "linux")]
+const ID3: Token = Token(2);
+
+#[cfg(target_os = "linux")]
+const DATA: &[u8] = b"Hello world!";
+
+#[test]
+fn is_send_and_sync() {
+    assert_send::<TcpSocket>();
+    assert_sync::<TcpSocket>();
+}
+
+#[test]
+fn set_reuseaddr() {
+    let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_reuseaddr(true).unwrap();
+    assert!(socket.reuseaddr().unwrap());
+
+    socket.bind(addr).unwrap();
+
+    let _ = socket.listen(128).unwrap();
+}
+
+#[test]
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+fn set_reuseport() {
+    let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_reuseport(true).unwrap();
+    assert!(socket.reuseport().unwrap());
+    socket.bind(addr).unwrap();
+    let addr = socket.local_addr().unwrap();
+    let _listener1 = socket.listen(128).unwrap();
+
+    // A second socket can bind the same address.
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_reuseport(true).unwrap();
+    socket.bind(addr).unwrap();
+    let _listener2 = socket.listen(128).unwrap();
+}
+
+#[test]
+fn set_buffer_sizes() {
+    let socket = TcpSocket::new_v4().unwrap();
+
+    socket.set_send_buffer_size(64 * 1024).unwrap();
+    // Some platforms (e.g. Linux) double the size, so we can't assert the
+    // exact value.
+    assert!(socket.send_buffer_size().unwrap() >= 64 * 1024);
+
+    socket.set_recv_buffer_size(64 * 1024).unwrap();
+    assert!(socket.recv_buffer_size().unwrap() >= 64 * 1024);
+}
+
+#[test]
+fn set_only_v6() {
+    let socket = match TcpSocket::new_v6() {
+        Ok(socket) => socket,
+        // IPv6 not supported.
+        Err(ref err) if err.kind() == io::ErrorKind::AddrNotAvailable => return,
+        Err(err) => panic!("unexpected error creating socket: {}", err),
+    };
+    socket.set_only_v6(true).unwrap();
+    assert!(socket.only_v6().unwrap());
+    socket.set_only_v6(false).unwrap();
+    assert!(!socket.only_v6().unwrap());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+fn set_freebind() {
+    let socket = TcpSocket::new_v4().unwrap();
+    assert!(!socket.freebind().unwrap());
+    socket.set_freebind(true).unwrap();
+    assert!(socket.freebind().unwrap());
+
+    // Binding to an address not assigned to any interface.
+    socket.bind("192.0.2.1:0".parse().unwrap()).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn set_bind_address_no_port() {
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_bind_address_no_port(true).unwrap();
+    assert!(socket.bind_address_no_port().unwrap());
+
+    socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    // The port is selected when connecting.
+    assert_eq!(socket.local_addr().unwrap().port(), 0);
+}
+
+#[test]
+fn bind_connect() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket = TcpSocket::new_for_addr(any_local_address()).unwrap();
+    socket.bind(any_local_address()).unwrap();
+    let mut listener = socket.listen(16).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    let local_addr = socket.local_addr().unwrap();
+    let mut stream = socket.connect(addr).unwrap();
+
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut stream, ID2, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![
+            ExpectEvent::new(ID1, Interest::READABLE),
+            ExpectEvent::new(ID2, Interest::WRITABLE),
+        ],
+    );
+
+    let (_accepted, peer_addr) = listener.accept().unwrap();
+    assert_eq!(peer_addr, local_addr);
+    assert_eq!(stream.local_addr().unwrap(), local_addr);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn connect_with_data() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    listener.set_fastopen(16).unwrap();
+    assert_eq!(listener.fastopen().unwrap(), 16);
+    let addr = listener.local_addr().unwrap();
+
+    let (mut stream, sent) = TcpStream::connect_with_data(addr, DATA).unwrap();
+    assert!(sent <= DATA.len());
+
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut stream, ID2, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![
+            ExpectEvent::new(ID1, Interest::READABLE),
+            ExpectEvent::new(ID2, Interest::WRITABLE),
+        ],
+    );
+    // Without a Fast Open cookie the data is written after connecting.
+    stream.write_all(&DATA[sent..]).unwrap();
+
+    let (mut accepted, _) = listener.accept().unwrap();
+    poll.registry()
+        .register(&mut accepted, ID3, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID3, Interest::READABLE)],
+    );
+    let mut buf = [0; 32];
+    let n = accepted.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA);
+}
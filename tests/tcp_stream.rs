@@ -9,6 +9,8 @@ use std::sync::{mpsc::channel, Arc, Barrier};
 use std::thread;
 use std::time::Duration;
 
+#[cfg(unix)]
+use mio::net::MsgFlags;
 use mio::net::TcpStream;
 use mio::{Interest, Token};
 
@@ -260,6 +262,342 @@ fn get_nodelay_without_previous_set() {
     thread_handle.join().expect("unable to join thread");
 }
 
+#[test]
+fn set_get_keepalive() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let barrier = Arc::new(Barrier::new(2));
+    let (thread_handle, address) = start_listener(1, Some(barrier.clone()), false);
+
+    let mut stream = TcpStream::connect(address).unwrap();
+
+    // on Windows: the stream must be connected before setting the keepalive.
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .expect("unable to register TCP stream");
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    assert!(!stream.keepalive().unwrap());
+    stream.set_keepalive(true).unwrap();
+    assert!(stream.keepalive().unwrap());
+    stream.set_keepalive(false).unwrap();
+    assert!(!stream.keepalive().unwrap());
+
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+    ))]
+    {
+        use mio::net::TcpKeepalive;
+
+        let keepalive = TcpKeepalive::new()
+            .with_time(Duration::from_secs(60))
+            .with_interval(Duration::from_secs(10))
+            .with_retries(5);
+        stream.set_keepalive_params(keepalive).unwrap();
+        assert!(stream.keepalive().unwrap());
+        assert_eq!(stream.keepalive_time().unwrap(), Duration::from_secs(60));
+        assert_eq!(
+            stream.keepalive_interval().unwrap(),
+            Duration::from_secs(10)
+        );
+        assert_eq!(stream.keepalive_retries().unwrap(), 5);
+
+        // Parameters that are not set are left unchanged.
+        let keepalive = TcpKeepalive::new().with_time(Duration::from_secs(120));
+        stream.set_keepalive_params(keepalive).unwrap();
+        assert_eq!(stream.keepalive_time().unwrap(), Duration::from_secs(120));
+        assert_eq!(
+            stream.keepalive_interval().unwrap(),
+            Duration::from_secs(10)
+        );
+
+        // Sub-second durations are rounded up, not down to zero.
+        let keepalive = TcpKeepalive::new()
+            .with_time(Duration::from_millis(500))
+            .with_interval(Duration::from_millis(1500));
+        stream.set_keepalive_params(keepalive).unwrap();
+        assert_eq!(stream.keepalive_time().unwrap(), Duration::from_secs(1));
+        assert_eq!(stream.keepalive_interval().unwrap(), Duration::from_secs(2));
+    }
+    assert!(stream.take_error().unwrap().is_none());
+
+    barrier.wait();
+    thread_handle.join().expect("unable to join thread");
+}
+
+#[test]
+fn set_get_linger_and_buffer_sizes() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let barrier = Arc::new(Barrier::new(2));
+    let (thread_handle, address) = start_listener(1, Some(barrier.clone()), false);
+
+    let mut stream = TcpStream::connect(address).unwrap();
+
+    // on Windows: the stream must be connected before setting options.
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .expect("unable to register TCP stream");
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    assert_eq!(stream.linger().unwrap(), None);
+    stream.set_linger(Some(Duration::from_secs(5))).unwrap();
+    assert_eq!(stream.linger().unwrap(), Some(Duration::from_secs(5)));
+    stream.set_linger(None).unwrap();
+    assert_eq!(stream.linger().unwrap(), None);
+
+    // The OS is free to adjust the buffer sizes.
+    stream.set_send_buffer_size(64 * 1024).unwrap();
+    assert!(stream.send_buffer_size().unwrap() >= 64 * 1024);
+    stream.set_recv_buffer_size(64 * 1024).unwrap();
+    assert!(stream.recv_buffer_size().unwrap() >= 64 * 1024);
+    assert!(stream.take_error().unwrap().is_none());
+
+    barrier.wait();
+    thread_handle.join().expect("unable to join thread");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn set_get_linux_tcp_options() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let barrier = Arc::new(Barrier::new(2));
+    let (thread_handle, address) = start_listener(1, Some(barrier.clone()), false);
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .expect("unable to register TCP stream");
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    stream.set_user_timeout(Duration::from_secs(30)).unwrap();
+    assert_eq!(stream.user_timeout().unwrap(), Duration::from_secs(30));
+
+    stream.set_cork(true).unwrap();
+    assert!(stream.cork().unwrap());
+    stream.set_cork(false).unwrap();
+    assert!(!stream.cork().unwrap());
+
+    stream.set_quickack(true).unwrap();
+    assert!(stream.quickack().unwrap());
+
+    stream.set_notsent_lowat(16 * 1024).unwrap();
+    assert_eq!(stream.notsent_lowat().unwrap(), 16 * 1024);
+
+    // The default algorithm can always be selected.
+    let algorithm = stream.congestion().unwrap();
+    assert!(!algorithm.is_empty());
+    stream.set_congestion(&algorithm).unwrap();
+    assert_eq!(stream.congestion().unwrap(), algorithm);
+    let err = stream.set_congestion("not-an-algorithm").unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOENT));
+    assert!(stream.take_error().unwrap().is_none());
+
+    barrier.wait();
+    thread_handle.join().expect("unable to join thread");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn tcp_info() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (thread_handle, address) = echo_listener(any_local_address(), 1);
+    let mut stream = TcpStream::connect(address).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE.add(Interest::READABLE))
+        .expect("unable to register TCP stream");
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    checked_write!(stream.write(DATA1));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; 16];
+    expect_read!(stream.read(&mut buf), DATA1);
+
+    let info = stream.tcp_info().unwrap();
+    assert!(info.rtt() > Duration::from_secs(0));
+    assert!(info.congestion_window() > 0);
+    assert!(info.send_mss() > 0);
+    assert_eq!(info.retransmits(), 0);
+    if let Some(min_rtt) = info.min_rtt() {
+        assert!(min_rtt <= info.rtt());
+    }
+
+    drop(stream);
+    thread_handle.join().expect("unable to join thread");
+}
+
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+))]
+fn send_file() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let path = util::temp_file("tcp_stream_send_file");
+    std::fs::write(&path, DATA1).unwrap();
+    let file = std::fs::File::open(&path).unwrap();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    let mut sent = 0;
+    while sent < DATA1_LEN {
+        sent += stream
+            .send_file(&file, sent as u64, DATA1_LEN - sent)
+            .unwrap();
+    }
+    assert_eq!(stream.send_file(&file, sent as u64, 10).unwrap(), 0);
+
+    let mut buf = [0; DATA1_LEN];
+    peer.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, DATA1);
+}
+
+#[test]
+#[cfg(unix)]
+fn send_recv_with_flags() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [0; 20];
+    assert_would_block(stream.recv_with_flags(&mut buf, MsgFlags::EMPTY));
+
+    peer.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    // Peeking leaves the data in the receive queue.
+    expect_read!(stream.recv_with_flags(&mut buf, MsgFlags::PEEK), DATA1);
+    expect_read!(stream.recv_with_flags(&mut buf, MsgFlags::EMPTY), DATA1);
+    assert_would_block(stream.recv_with_flags(&mut buf, MsgFlags::PEEK));
+
+    checked_write!(stream.send_with_flags(DATA2, MsgFlags::EMPTY));
+    peer.read_exact(&mut buf[..DATA2_LEN]).unwrap();
+    assert_eq!(&buf[..DATA2_LEN], DATA2);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn send_zerocopy() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    assert!(!stream.zerocopy().unwrap());
+    stream.set_zerocopy(true).unwrap();
+    assert!(stream.zerocopy().unwrap());
+    assert_would_block(stream.recv_zerocopy_completion());
+
+    checked_write!(stream.send_zerocopy(DATA1));
+    checked_write!(stream.send_zerocopy(DATA2));
+    // The completion looks like the socket's write side is closed.
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(
+            ID1,
+            Readiness::ERROR | Readiness::WRITE_CLOSED,
+        )],
+    );
+
+    // The completions may be merged, or arrive separately.
+    let mut completed = Vec::new();
+    while completed.len() < 2 {
+        match stream.recv_zerocopy_completion() {
+            Ok(completion) => completed.extend(completion.iter()),
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                poll.poll(&mut events, Some(Duration::from_millis(100)))
+                    .unwrap();
+            }
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+    assert_eq!(completed, vec![0, 1]);
+    assert!(stream.take_error().unwrap().is_none());
+
+    let mut buf = [0; DATA1_LEN + DATA2_LEN];
+    peer.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf[..DATA1_LEN], DATA1);
+    assert_eq!(&buf[DATA1_LEN..], DATA2);
+
+    // The connection is still usable after the completion.
+    checked_write!(stream.write(DATA1));
+    peer.read_exact(&mut buf[..DATA1_LEN]).unwrap();
+    assert_eq!(&buf[..DATA1_LEN], DATA1);
+    peer.write_all(DATA2).unwrap();
+    poll.registry()
+        .reregister(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    expect_read!(stream.read(&mut buf), DATA2);
+}
+
 #[test]
 fn shutdown_read() {
     let (mut poll, mut events) = init_with_poll();
@@ -2,8 +2,14 @@
 #![cfg(all(feature = "os-poll", feature = "net"))]
 
 use log::{debug, info};
+#[cfg(unix)]
+use mio::net::MsgFlags;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use mio::net::RecvMeta;
 use mio::net::UdpSocket;
 use mio::{Events, Interest, Poll, Registry, Token};
+#[cfg(unix)]
+use std::io::{IoSlice, IoSliceMut};
 use std::net::{self, IpAddr, SocketAddr};
 #[cfg(unix)]
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
@@ -181,6 +187,306 @@ fn set_get_ttl() {
     assert!(socket1.take_error().unwrap().is_none());
 }
 
+#[test]
+#[cfg(unix)]
+fn send_recv_with_flags() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+    poll.registry()
+        .register(&mut socket2, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [0; 20];
+    assert_would_block(socket2.recv_from_with_flags(&mut buf, MsgFlags::EMPTY));
+
+    checked_write!(socket1.send_to_with_flags(DATA1, address2, MsgFlags::EMPTY));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    // Peeking leaves the datagram in the receive queue.
+    let (n, address) = socket2
+        .recv_from_with_flags(&mut buf, MsgFlags::PEEK)
+        .unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(address, address1);
+    let (n, address) = socket2
+        .recv_from_with_flags(&mut buf, MsgFlags::EMPTY)
+        .unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(address, address1);
+
+    socket2.connect(address1).unwrap();
+    socket1.connect(address2).unwrap();
+    checked_write!(socket1.send_with_flags(DATA2, MsgFlags::EMPTY));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    // With `MSG_TRUNC` Linux returns the full length of the datagram.
+    #[cfg(target_os = "linux")]
+    assert_eq!(
+        socket2
+            .recv_with_flags(&mut buf[..4], MsgFlags::PEEK | MsgFlags::TRUNC)
+            .unwrap(),
+        DATA2.len()
+    );
+    expect_read!(socket2.recv_with_flags(&mut buf, MsgFlags::EMPTY), DATA2);
+    assert_would_block(socket2.recv_with_flags(&mut buf, MsgFlags::EMPTY));
+}
+
+#[test]
+#[cfg(unix)]
+fn send_recv_vectored() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+    poll.registry()
+        .register(&mut socket2, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut header = [0; 6];
+    let mut body = [0; 20];
+    assert_would_block(
+        socket2.recv_from_vectored(&mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut body)]),
+    );
+
+    // Both buffers are sent as a single datagram.
+    let bufs = [IoSlice::new(&DATA1[..6]), IoSlice::new(&DATA1[6..])];
+    assert_eq!(
+        socket1.send_to_vectored(&bufs, address2).unwrap(),
+        DATA1.len()
+    );
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let (n, address) = socket2
+        .recv_from_vectored(&mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut body)])
+        .unwrap();
+    assert_eq!(n, DATA1.len());
+    assert_eq!(address, address1);
+    assert_eq!(&header, &DATA1[..6]);
+    assert_eq!(&body[..n - 6], &DATA1[6..]);
+
+    socket1.connect(address2).unwrap();
+    socket2.connect(address1).unwrap();
+    let bufs = [IoSlice::new(&DATA2[..6]), IoSlice::new(&DATA2[6..])];
+    assert_eq!(socket1.send_vectored(&bufs).unwrap(), DATA2.len());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let n = socket2
+        .recv_vectored(&mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut body)])
+        .unwrap();
+    assert_eq!(n, DATA2.len());
+    assert_eq!(&header, &DATA2[..6]);
+    assert_eq!(&body[..n - 6], &DATA2[6..]);
+    assert_would_block(socket2.recv_vectored(&mut [IoSliceMut::new(&mut body)]));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn send_recv_segmented() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+    poll.registry()
+        .register(&mut socket2, ID1, Interest::READABLE)
+        .unwrap();
+
+    assert!(!socket2.gro().unwrap());
+    socket2.set_gro(true).unwrap();
+    assert!(socket2.gro().unwrap());
+
+    let mut buf = [0; 64];
+    assert_would_block(socket2.recv_with_meta(&mut buf));
+
+    // Three datagrams of 4 bytes and one of 2 bytes.
+    const DATA: &[u8] = b"abcdefghijklmn";
+    assert_eq!(
+        socket1.send_to_segmented(DATA, address2, 4).unwrap(),
+        DATA.len()
+    );
+    socket1.connect(address2).unwrap();
+    assert_eq!(socket1.send_segmented(DATA, 4).unwrap(), DATA.len());
+
+    // The kernel may or may not coalesce the datagrams.
+    let mut received = Vec::new();
+    while received.len() < 2 * DATA.len() {
+        match socket2.recv_with_meta(&mut buf) {
+            Ok(meta) => {
+                assert_eq!(meta.addr(), address1);
+                assert!(!meta.is_truncated());
+                assert!(!meta.is_control_truncated());
+                if let Some(segment_size) = meta.segment_size() {
+                    assert_eq!(segment_size, 4);
+                } else {
+                    assert!(meta.len() <= 4);
+                }
+                received.extend_from_slice(&buf[..meta.len()]);
+            }
+            Err(ref err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                poll.poll(&mut events, Some(Duration::from_millis(100)))
+                    .unwrap();
+            }
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+    assert_eq!(&received[..DATA.len()], DATA);
+    assert_eq!(&received[DATA.len()..], DATA);
+
+    // A datagram larger than the buffer is truncated.
+    assert_eq!(socket1.send(DATA).unwrap(), DATA.len());
+    let mut buf = [0; 4];
+    loop {
+        match socket2.recv_with_meta(&mut buf) {
+            Ok(meta) => {
+                assert_eq!(meta.len(), buf.len());
+                assert!(meta.is_truncated());
+                assert_eq!(&buf, &DATA[..4]);
+                break;
+            }
+            Err(ref err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                poll.poll(&mut events, Some(Duration::from_millis(100)))
+                    .unwrap();
+            }
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn send_recv_many() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+    poll.registry()
+        .register(&mut socket2, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut storage = [[0; 20]; 4];
+    let mut meta = [RecvMeta::default(); 4];
+    {
+        let mut bufs: Vec<_> = storage.iter_mut().map(|b| IoSliceMut::new(b)).collect();
+        assert_would_block(socket2.recv_many(&mut bufs, &mut meta));
+    }
+
+    let bufs = [
+        IoSlice::new(DATA1),
+        IoSlice::new(DATA2),
+        IoSlice::new(DATA1),
+    ];
+    let err = socket1.send_many(&bufs, &[address2]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(
+        socket1
+            .send_many(&bufs, &[address2, address2, address2])
+            .unwrap(),
+        3
+    );
+    socket1.connect(address2).unwrap();
+    assert_eq!(socket1.send_many(&bufs[1..], &[]).unwrap(), 2);
+
+    // The datagrams may not all be received at once.
+    let mut received = Vec::new();
+    while received.len() < 5 {
+        let mut bufs: Vec<_> = storage.iter_mut().map(|b| IoSliceMut::new(b)).collect();
+        match socket2.recv_many(&mut bufs, &mut meta) {
+            Ok(n) => {
+                for (buf, meta) in bufs.iter().zip(meta.iter()).take(n) {
+                    assert_eq!(meta.addr(), address1);
+                    received.push(buf[..meta.len()].to_vec());
+                }
+            }
+            Err(ref err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                poll.poll(&mut events, Some(Duration::from_millis(100)))
+                    .unwrap();
+            }
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+    assert_eq!(received, vec![DATA1, DATA2, DATA1, DATA2, DATA1]);
+
+    // The batch size is only limited by the number of buffers.
+    let bufs = vec![IoSlice::new(DATA1); 40];
+    assert_eq!(socket1.send_many(&bufs, &[]).unwrap(), 40);
+    let mut storage = vec![[0; 20]; 40];
+    let mut meta = vec![RecvMeta::default(); 40];
+    let mut received = 0;
+    while received < 40 {
+        let mut bufs: Vec<_> = storage.iter_mut().map(|b| IoSliceMut::new(b)).collect();
+        match socket2.recv_many(&mut bufs[received..], &mut meta[received..]) {
+            Ok(n) => received += n,
+            Err(ref err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                poll.poll(&mut events, Some(Duration::from_millis(100)))
+                    .unwrap();
+            }
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+    assert!(meta.iter().all(|meta| meta.len() == DATA1.len()));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn send_zerocopy() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    socket1.connect(socket2.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    socket1.set_zerocopy(true).unwrap();
+    assert!(socket1.zerocopy().unwrap());
+    assert_would_block(socket1.recv_zerocopy_completion());
+
+    checked_write!(socket1.send_zerocopy(DATA1));
+    let completion = loop {
+        match socket1.recv_zerocopy_completion() {
+            Ok(completion) => break completion,
+            Err(ref err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                poll.poll(&mut events, Some(Duration::from_millis(100)))
+                    .unwrap();
+            }
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    };
+    assert_eq!((completion.start(), completion.end()), (0, 0));
+    assert_eq!(completion.iter().collect::<Vec<_>>(), vec![0]);
+
+    let mut buf = [0; 20];
+    let n = socket2.recv(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+}
+
 #[test]
 fn get_ttl_without_previous_set() {
     let socket1 = UdpSocket::bind(any_local_address()).unwrap();
@@ -1,8 +1,8 @@
 #![cfg(all(unix, feature = "os-poll", feature = "net"))]
 
-use mio::net::UnixDatagram;
+use mio::net::{MsgFlags, UnixDatagram};
 use mio::{Interest, Token};
-use std::io;
+use std::io::{self, IoSlice, IoSliceMut};
 use std::net::Shutdown;
 use std::os::unix::net;
 
@@ -165,6 +165,77 @@ fn unix_datagram_pair() {
     assert!(datagram2.take_error().unwrap().is_none());
 }
 
+#[test]
+fn unix_datagram_send_recv_with_flags() {
+    let path1 = temp_file("unix_datagram_send_recv_with_flags1");
+    let path2 = temp_file("unix_datagram_send_recv_with_flags2");
+    let datagram1 = UnixDatagram::bind(&path1).unwrap();
+    let datagram2 = UnixDatagram::bind(&path2).unwrap();
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_would_block(datagram2.recv_from_with_flags(&mut buf, MsgFlags::EMPTY));
+
+    checked_write!(datagram1.send_to_with_flags(DATA1, &path2, MsgFlags::EMPTY));
+    // Peeking leaves the datagram in the receive queue.
+    let (n, address) = datagram2
+        .recv_from_with_flags(&mut buf, MsgFlags::PEEK)
+        .unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(address.as_pathname(), Some(&*path1));
+    let (n, address) = datagram2
+        .recv_from_with_flags(&mut buf, MsgFlags::EMPTY)
+        .unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(address.as_pathname(), Some(&*path1));
+
+    datagram1.connect(&path2).unwrap();
+    datagram2.connect(&path1).unwrap();
+    checked_write!(datagram2.send_with_flags(DATA2, MsgFlags::EMPTY));
+    expect_read!(datagram1.recv_with_flags(&mut buf, MsgFlags::EMPTY), DATA2);
+    assert_would_block(datagram1.recv_with_flags(&mut buf, MsgFlags::EMPTY));
+}
+
+#[test]
+fn unix_datagram_send_recv_vectored() {
+    let path1 = temp_file("unix_datagram_send_recv_vectored1");
+    let path2 = temp_file("unix_datagram_send_recv_vectored2");
+    let datagram1 = UnixDatagram::bind(&path1).unwrap();
+    let datagram2 = UnixDatagram::bind(&path2).unwrap();
+
+    let mut header = [0; 6];
+    let mut body = [0; DEFAULT_BUF_SIZE];
+    assert_would_block(
+        datagram2
+            .recv_from_vectored(&mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut body)]),
+    );
+
+    // Both buffers are sent as a single datagram.
+    let bufs = [IoSlice::new(&DATA1[..6]), IoSlice::new(&DATA1[6..])];
+    assert_eq!(
+        datagram1.send_to_vectored(&bufs, &path2).unwrap(),
+        DATA1.len()
+    );
+    let (n, address) = datagram2
+        .recv_from_vectored(&mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut body)])
+        .unwrap();
+    assert_eq!(n, DATA1.len());
+    assert_eq!(address.as_pathname(), Some(&*path1));
+    assert_eq!(&header, &DATA1[..6]);
+    assert_eq!(&body[..n - 6], &DATA1[6..]);
+
+    datagram1.connect(&path2).unwrap();
+    datagram2.connect(&path1).unwrap();
+    let bufs = [IoSlice::new(&DATA2[..6]), IoSlice::new(&DATA2[6..])];
+    assert_eq!(datagram2.send_vectored(&bufs).unwrap(), DATA2.len());
+    let n = datagram1
+        .recv_vectored(&mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut body)])
+        .unwrap();
+    assert_eq!(n, DATA2.len());
+    assert_eq!(&header, &DATA2[..6]);
+    assert_eq!(&body[..n - 6], &DATA2[6..]);
+    assert_would_block(datagram1.recv_vectored(&mut [IoSliceMut::new(&mut body)]));
+}
+
 #[test]
 fn unix_datagram_shutdown() {
     let (mut poll, mut events) = init_with_poll();
@@ -146,6 +146,28 @@ fn unix_listener_abstract_namesapce() {
     );
 }
 
+#[test]
+fn unix_listener_accept_many() {
+    let path = temp_file("unix_listener_accept_many");
+    let listener = UnixListener::bind(&path).unwrap();
+
+    let mut accepted = Vec::new();
+    assert_would_block(listener.accept_many(&mut accepted, 2));
+    assert!(accepted.is_empty());
+
+    let _streams: Vec<_> = (0..3)
+        .map(|_| net::UnixStream::connect(&path).unwrap())
+        .collect();
+
+    // Limited to `max` connections.
+    assert_eq!(listener.accept_many(&mut accepted, 2).unwrap(), 2);
+    assert_eq!(accepted.len(), 2);
+    // Stops at the last pending connection.
+    assert_eq!(listener.accept_many(&mut accepted, 2).unwrap(), 1);
+    assert_eq!(accepted.len(), 3);
+    assert_would_block(listener.accept_many(&mut accepted, 2));
+}
+
 fn smoke_test<F>(new_listener: F, test_name: &'static str)
 where
     F: FnOnce(&Path) -> io::Result<UnixListener>,
@@ -215,3 +215,13 @@ pub fn expect_one_closed_event(poll: &mut Poll, events: &mut Events, token: Toke
     }
     assert!(iter.next().is_none());
 }
+
+#[test]
+fn take_error() {
+    let (sender, receiver) = pipe::new().unwrap();
+    assert!(sender.take_error().unwrap().is_none());
+    assert!(receiver.take_error().unwrap().is_none());
+
+    drop(receiver);
+    assert!(sender.take_error().unwrap().is_none());
+}
@@ -1,6 +1,6 @@
 #![cfg(all(unix, feature = "os-poll", feature = "net"))]
 
-use mio::net::UnixStream;
+use mio::net::{ConnectStatus, MsgFlags, UnixStream};
 use mio::{Interest, Token};
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
 use std::net::Shutdown;
@@ -66,6 +66,7 @@ fn unix_stream_connect() {
         &mut events,
         vec![ExpectEvent::new(TOKEN_1, Interest::WRITABLE)],
     );
+    assert_eq!(stream.finish_connect().unwrap(), ConnectStatus::Connected);
 
     barrier.wait();
     expect_events(
@@ -124,6 +125,101 @@ fn unix_stream_pair() {
     assert_would_block(s2.read(&mut buf));
 }
 
+#[test]
+fn unix_stream_send_recv_with_flags() {
+    let (s1, s2) = UnixStream::pair().unwrap();
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_would_block(s2.recv_with_flags(&mut buf, MsgFlags::EMPTY));
+
+    checked_write!(s1.send_with_flags(DATA1, MsgFlags::EMPTY));
+    // Peeking leaves the data in the receive queue.
+    expect_read!(s2.recv_with_flags(&mut buf, MsgFlags::PEEK), DATA1);
+    expect_read!(s2.recv_with_flags(&mut buf, MsgFlags::EMPTY), DATA1);
+    assert_would_block(s2.recv_with_flags(&mut buf, MsgFlags::EMPTY));
+}
+
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+))]
+fn unix_stream_send_file() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let path = temp_file("unix_stream_send_file");
+    std::fs::write(&path, DATA1).unwrap();
+    let file = std::fs::File::open(&path).unwrap();
+
+    let (s1, mut s2) = UnixStream::pair().unwrap();
+    poll.registry()
+        .register(&mut s2, TOKEN_2, Interest::READABLE)
+        .unwrap();
+
+    assert_eq!(s1.send_file(&file, 6, DATA1_LEN).unwrap(), DATA1_LEN - 6);
+    // At the end of the file.
+    assert_eq!(s1.send_file(&file, DATA1_LEN as u64, 10).unwrap(), 0);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_2, Interest::READABLE)],
+    );
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    expect_read!(s2.read(&mut buf), &DATA1[6..]);
+}
+
+#[test]
+#[cfg(all(feature = "os-ext", target_os = "linux"))]
+fn unix_stream_splice() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut s1, mut s2) = UnixStream::pair().unwrap();
+    let (sender, mut receiver) = mio::unix::pipe::new().unwrap();
+    poll.registry()
+        .register(&mut s2, TOKEN_2, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut receiver, Token(2), Interest::READABLE)
+        .unwrap();
+
+    // Stream to pipe.
+    assert_would_block(s2.splice_to(&sender, DEFAULT_BUF_SIZE));
+    checked_write!(s1.write(DATA1));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_2, Interest::READABLE)],
+    );
+    assert_eq!(s2.splice_to(&sender, DEFAULT_BUF_SIZE).unwrap(), DATA1_LEN);
+
+    // Pipe to stream.
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(Token(2), Interest::READABLE)],
+    );
+    assert_eq!(
+        s2.splice_from(&receiver, DEFAULT_BUF_SIZE).unwrap(),
+        DATA1_LEN
+    );
+    assert_would_block(s2.splice_from(&receiver, DEFAULT_BUF_SIZE));
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    poll.registry()
+        .register(&mut s1, TOKEN_1, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+    expect_read!(s1.read(&mut buf), DATA1);
+}
+
 #[test]
 fn unix_stream_peer_addr() {
     init();